@@ -0,0 +1,285 @@
+//! Command line tool for inspecting, recording and replaying the MumbleLink.
+
+use clap::{Parser, Subcommand};
+use gw2_mumble::{
+    map_id,
+    recording::{Recorder, Replayer},
+    Event, LinkSource, LinkedMem, MumbleLink, MumbleWriter, Position, Watcher,
+};
+use std::{
+    error::Error,
+    fs::File,
+    io::BufWriter,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Interval between flushes of a recording in progress.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Inspect, record and replay the Guild Wars 2 MumbleLink.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    /// Name of the MumbleLink, defaults to the game's `-mumble` argument or "MumbleLink".
+    #[arg(long, global = true)]
+    link: Option<String>,
+
+    /// Path of a file backing the MumbleLink instead of shared memory.
+    #[cfg(unix)]
+    #[arg(long, global = true, conflicts_with = "link")]
+    file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the current MumbleLink contents.
+    Dump {
+        /// Print as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print changes as they happen.
+    Watch {
+        /// Poll interval in milliseconds.
+        #[arg(long, default_value_t = 20)]
+        interval: u64,
+    },
+
+    /// Record a session into a file.
+    Record {
+        /// Output file.
+        output: PathBuf,
+
+        /// Stop after the given number of seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        duration: Option<Duration>,
+
+        /// Poll interval in milliseconds.
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+
+    /// Play back a recorded session into the MumbleLink.
+    Replay {
+        /// Input file.
+        input: PathBuf,
+
+        /// Playback speed multiplier.
+        #[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+        speed: f64,
+
+        /// Restart playback at the end.
+        #[arg(long = "loop")]
+        looping: bool,
+    },
+
+    /// Print decoded information about the current state.
+    Info,
+}
+
+impl Cli {
+    fn open(&self) -> Result<MumbleLink, gw2_mumble::Error> {
+        #[cfg(unix)]
+        if let Some(path) = &self.file {
+            return MumbleLink::open_path(path);
+        }
+        MumbleLink::with_name(&self.link_name())
+    }
+
+    fn open_writer(&self) -> Result<MumbleWriter, gw2_mumble::Error> {
+        #[cfg(unix)]
+        if let Some(path) = &self.file {
+            return MumbleWriter::create_path(path);
+        }
+        MumbleWriter::with_name(&self.link_name())
+    }
+
+    fn link_name(&self) -> String {
+        self.link.clone().unwrap_or_else(MumbleLink::link_name)
+    }
+}
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let secs = arg.parse::<f64>().map_err(|err| err.to_string())?;
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| "expected a finite non-negative number of seconds".into())
+}
+
+fn parse_speed(arg: &str) -> Result<f64, String> {
+    let speed = arg.parse::<f64>().map_err(|err| err.to_string())?;
+    if speed.is_finite() && speed > 0.0 {
+        Ok(speed)
+    } else {
+        Err("expected a positive number".into())
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    match &cli.command {
+        Command::Dump { json } => {
+            let mem = cli.open()?.read_consistent().into_inner();
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&mem)?);
+            } else {
+                dump(&mem);
+            }
+        }
+        Command::Watch { interval } => {
+            let link = cli.open()?;
+            let mut watcher = Watcher::new();
+            loop {
+                for event in watcher.poll(&link) {
+                    print_event(&event);
+                }
+                thread::sleep(Duration::from_millis(*interval));
+            }
+        }
+        Command::Record {
+            output,
+            duration,
+            interval,
+        } => {
+            let link = cli.open()?;
+            let writer = BufWriter::new(File::create(output)?);
+            let mut recorder = Recorder::new(link.as_mumble_ptr(), writer)?;
+            let mut frames = 0;
+            let mut flushed = Instant::now();
+            while duration.is_none_or(|duration| recorder.elapsed() < duration) {
+                if recorder.record()? {
+                    frames += 1;
+                }
+                if flushed.elapsed() >= FLUSH_INTERVAL {
+                    recorder.flush()?;
+                    flushed = Instant::now();
+                }
+                thread::sleep(Duration::from_millis(*interval));
+            }
+            recorder.flush()?;
+            eprintln!("recorded {frames} frames");
+        }
+        Command::Replay {
+            input,
+            speed,
+            looping,
+        } => {
+            let mut replayer = Replayer::from_reader(File::open(input)?)?;
+            replayer.set_speed(*speed);
+            replayer.set_looping(*looping);
+            let header = replayer.header();
+            eprintln!(
+                "replaying {} frames over {:.1}s recorded with version {} on build {}",
+                replayer.frame_count(),
+                replayer.duration().as_secs_f64(),
+                header.crate_version,
+                header.build_id
+            );
+            let mut replayer = replayer.with_writer(cli.open_writer()?);
+            loop {
+                replayer.update();
+                match replayer.time_to_next_frame() {
+                    Some(wait) => thread::sleep(wait),
+                    None => break,
+                }
+            }
+        }
+        Command::Info => info(&cli.open()?),
+    }
+    Ok(())
+}
+
+fn dump(mem: &LinkedMem) {
+    let context = mem.typed_context();
+    println!("ui_version: {}", mem.ui_version);
+    println!("ui_tick: {}", mem.ui_tick);
+    println!("name: {}", mem.name_string_lossy());
+    print_position("avatar", &mem.avatar);
+    print_position("camera", &mem.camera);
+    println!("identity: {}", mem.identity_string_lossy());
+    println!("context_len: {}", mem.context_len);
+    match context.server_socket_addr() {
+        Some(addr) => println!("server_address: {addr}"),
+        None => println!("server_address: none"),
+    }
+    println!("map_id: {}", context.map_id);
+    println!("map_type: {}", context.map_type);
+    println!("shard_id: {}", context.shard_id);
+    println!("instance: {}", context.instance);
+    println!("build_id: {}", context.build_id);
+    println!("ui_state: {:?}", context.ui_state);
+    println!(
+        "compass: {}x{} rotation {}",
+        context.compass_width, context.compass_height, context.compass_rotation
+    );
+    println!("player: {} {}", context.player_x, context.player_y);
+    println!(
+        "map_center: {} {} scale {}",
+        context.map_center_x, context.map_center_y, context.map_scale
+    );
+    println!("process_id: {}", context.process_id);
+    println!("mount: {}", context.mount_index);
+    println!("description: {}", mem.description_string_lossy());
+}
+
+fn print_position(label: &str, pos: &Position) {
+    println!(
+        "{label}: position {:?} front {:?} top {:?}",
+        pos.position, pos.front, pos.top
+    );
+}
+
+fn print_event(event: &Event) {
+    match event {
+        Event::MapChanged { from, to } => println!("map: {} -> {}", map_name(*from), map_name(*to)),
+        Event::MountChanged { from, to } => println!("mount: {from} -> {to}"),
+        Event::UiStateChanged { added, removed } => {
+            println!("ui_state: added {added:?}, removed {removed:?}")
+        }
+        Event::IdentityChanged { to, .. } => println!("identity: {to}"),
+        Event::BuildChanged { from, to } => println!("build: {from} -> {to}"),
+        Event::InstanceChanged { from, to } => println!("instance: {from} -> {to}"),
+        Event::ServerChanged { to, .. } => match gw2_mumble::decode_sockaddr(to) {
+            Some(addr) => println!("server: {addr}"),
+            None => println!("server: none"),
+        },
+        Event::GameClosed => println!("game closed"),
+    }
+}
+
+fn map_name(id: u32) -> String {
+    match map_id::lookup(id) {
+        Some(map) => format!("{} ({id})", map.name),
+        None => id.to_string(),
+    }
+}
+
+fn info(link: &impl LinkSource) {
+    let context = link.read_context();
+    match context.server_socket_addr() {
+        Some(addr) => println!("server: {addr}"),
+        None => println!("server: none"),
+    }
+    println!("map: {}", map_name(context.map_id));
+    println!("map type: {}", context.map_type);
+    println!("mount: {}", context.mount_index);
+    let flags: Vec<_> = context
+        .ui_state
+        .iter_names()
+        .map(|(name, _)| name)
+        .collect();
+    println!("ui state: {}", flags.join(", "));
+    match link.decode_identity() {
+        Ok(identity) => {
+            println!("character: {}", identity.name);
+            println!("profession: {}", identity.display_profession());
+            println!("commander: {}", identity.commander);
+        }
+        Err(err) => println!("identity: {err}"),
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{
+    math::{add, cross, dot, normalize_or, scale, sub},
+    Identity, LinkedMem, Position,
+};
+
+/// Camera derived from the MumbleLink camera [`Position`] and [`Identity::fov`].
+///
+/// Uses the left-handed coordinate system of the game with `y` pointing up.
+/// Matrices are column-major with column vectors and map depth to `0..1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Position of the camera.
+    pub position: [f32; 3],
+
+    /// Normalized direction the camera is facing.
+    pub forward: [f32; 3],
+
+    /// Normalized direction to the right of the camera.
+    pub right: [f32; 3],
+
+    /// Normalized direction to the top of the camera.
+    pub up: [f32; 3],
+
+    /// Vertical field of view in radians.
+    pub fov: f32,
+
+    /// Distance of the near clipping plane.
+    pub near: f32,
+
+    /// Distance of the far clipping plane.
+    pub far: f32,
+}
+
+impl Camera {
+    /// Default distance of the near clipping plane.
+    pub const DEFAULT_NEAR: f32 = 0.1;
+
+    /// Default distance of the far clipping plane.
+    pub const DEFAULT_FAR: f32 = 10_000.0;
+
+    /// Creates a new camera from the camera [`Position`] and vertical field of view in radians.
+    ///
+    /// The up vector is made orthogonal to the front vector.
+    /// A zero front vector falls back to facing `+z` and a zero or parallel top vector falls back to `+y` up.
+    pub fn new(camera: &Position, fov: f32) -> Self {
+        const X: [f32; 3] = [1.0, 0.0, 0.0];
+        const Y: [f32; 3] = [0.0, 1.0, 0.0];
+        const Z: [f32; 3] = [0.0, 0.0, 1.0];
+
+        let forward = normalize_or(camera.front, Z);
+        let right = normalize_or(
+            cross(camera.top, forward),
+            normalize_or(cross(Y, forward), X),
+        );
+        let up = cross(forward, right);
+        Self {
+            position: camera.position,
+            forward,
+            right,
+            up,
+            fov,
+            near: Self::DEFAULT_NEAR,
+            far: Self::DEFAULT_FAR,
+        }
+    }
+
+    /// Creates a new camera from the [`LinkedMem`] camera and the [`Identity`] field of view.
+    #[inline]
+    pub fn from_link(mem: &LinkedMem, identity: &Identity) -> Self {
+        Self::new(&mem.camera, identity.fov)
+    }
+
+    /// Returns the camera with the given clipping plane distances.
+    #[inline]
+    pub fn with_clip(self, near: f32, far: f32) -> Self {
+        Self { near, far, ..self }
+    }
+
+    /// Returns the left-handed view matrix.
+    pub fn view_matrix(&self) -> [[f32; 4]; 4] {
+        let [right, up, forward] = [self.right, self.up, self.forward];
+        [
+            [right[0], up[0], forward[0], 0.0],
+            [right[1], up[1], forward[1], 0.0],
+            [right[2], up[2], forward[2], 0.0],
+            [
+                -dot(right, self.position),
+                -dot(up, self.position),
+                -dot(forward, self.position),
+                1.0,
+            ],
+        ]
+    }
+
+    /// Returns the left-handed perspective projection matrix for the given viewport size.
+    pub fn projection_matrix(&self, viewport: [f32; 2]) -> [[f32; 4]; 4] {
+        let [x_scale, y_scale] = self.scales(viewport);
+        let depth = self.far / (self.far - self.near);
+        [
+            [x_scale, 0.0, 0.0, 0.0],
+            [0.0, y_scale, 0.0, 0.0],
+            [0.0, 0.0, depth, 1.0],
+            [0.0, 0.0, -depth * self.near, 0.0],
+        ]
+    }
+
+    /// Returns the combined view projection matrix for the given viewport size.
+    pub fn view_projection_matrix(&self, viewport: [f32; 2]) -> [[f32; 4]; 4] {
+        let view = self.view_matrix();
+        let projection = self.projection_matrix(viewport);
+        let mut result = [[0.0; 4]; 4];
+        for (col, result) in view.iter().zip(&mut result) {
+            for (row, value) in result.iter_mut().enumerate() {
+                *value = (0..4).map(|i| projection[i][row] * col[i]).sum();
+            }
+        }
+        result
+    }
+
+    /// Transforms a world point into view space.
+    ///
+    /// The `z` component is the distance along the facing direction.
+    #[inline]
+    pub fn to_view(&self, point: [f32; 3]) -> [f32; 3] {
+        let offset = sub(point, self.position);
+        [
+            dot(offset, self.right),
+            dot(offset, self.up),
+            dot(offset, self.forward),
+        ]
+    }
+
+    /// Projects a world point onto the screen with the given viewport size.
+    pub fn project(&self, point: [f32; 3], viewport: [f32; 2]) -> Projection {
+        let [x, y, z] = self.to_view(point);
+        let [x_scale, y_scale] = self.scales(viewport);
+        let ndc_x = x * x_scale / z;
+        let ndc_y = y * y_scale / z;
+        Projection {
+            screen: [0.5 * (ndc_x + 1.0), 0.5 * (1.0 - ndc_y)],
+            depth: z,
+            behind: z <= 0.0,
+        }
+    }
+
+    /// Returns the ray through the given normalized screen coordinates for picking.
+    ///
+    /// This is the inverse of [`Camera::project`].
+    pub fn ray(&self, screen: [f32; 2], viewport: [f32; 2]) -> Ray {
+        let [x_scale, y_scale] = self.scales(viewport);
+        let ndc_x = 2.0 * screen[0] - 1.0;
+        let ndc_y = 1.0 - 2.0 * screen[1];
+        let direction = add(
+            self.forward,
+            add(
+                scale(self.right, ndc_x / x_scale),
+                scale(self.up, ndc_y / y_scale),
+            ),
+        );
+        Ray {
+            origin: self.position,
+            direction: normalize_or(direction, self.forward),
+        }
+    }
+
+    /// Returns the horizontal and vertical projection scales.
+    #[inline]
+    fn scales(&self, viewport: [f32; 2]) -> [f32; 2] {
+        let y_scale = (0.5 * self.fov).tan().recip();
+        let aspect = viewport[0] / viewport[1];
+        [y_scale / aspect, y_scale]
+    }
+}
+
+/// World point projected onto the screen via [`Camera::project`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    /// Normalized screen coordinates with the origin in the top left.
+    ///
+    /// Visible points are in `0..1`.
+    pub screen: [f32; 2],
+
+    /// Distance along the facing direction of the camera.
+    pub depth: f32,
+
+    /// Whether the point is behind the camera.
+    ///
+    /// Screen coordinates of points behind the camera are mirrored.
+    pub behind: bool,
+}
+
+impl Projection {
+    /// Returns the screen coordinates in pixels for the given viewport size.
+    #[inline]
+    pub fn to_pixels(&self, viewport: [f32; 2]) -> [f32; 2] {
+        [self.screen[0] * viewport[0], self.screen[1] * viewport[1]]
+    }
+
+    /// Returns whether the point is in front of the camera and within the screen.
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        !self.behind && self.screen.iter().all(|value| (0.0..=1.0).contains(value))
+    }
+}
+
+/// Ray in world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// Origin of the ray.
+    pub origin: [f32; 3],
+
+    /// Normalized direction of the ray.
+    pub direction: [f32; 3],
+}
+
+impl Ray {
+    /// Returns the point at the given distance along the ray.
+    #[inline]
+    pub fn at(&self, distance: f32) -> [f32; 3] {
+        add(self.origin, scale(self.direction, distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const VIEWPORT: [f32; 2] = [200.0, 100.0];
+
+    fn camera(front: [f32; 3], top: [f32; 3]) -> Camera {
+        Camera::new(&Position::new([1.0, 2.0, 3.0], front, top), FRAC_PI_2).with_clip(1.0, 101.0)
+    }
+
+    fn transform(matrix: [[f32; 4]; 4], point: [f32; 3]) -> [f32; 4] {
+        let point = [point[0], point[1], point[2], 1.0];
+        std::array::from_fn(|row| (0..4).map(|col| matrix[col][row] * point[col]).sum())
+    }
+
+    fn assert_close<const N: usize>(actual: [f32; N], expected: [f32; N]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-5),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn matrices() {
+        let camera = camera([0.0, 0.0, 2.0], [0.0, 3.0, 0.0]);
+        assert_eq!(camera.right, [1.0, 0.0, 0.0]);
+        assert_eq!(camera.up, [0.0, 1.0, 0.0]);
+        assert_eq!(camera.forward, [0.0, 0.0, 1.0]);
+
+        assert_eq!(
+            camera.view_matrix(),
+            [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [-1.0, -2.0, -3.0, 1.0],
+            ]
+        );
+        assert_close(
+            camera
+                .projection_matrix(VIEWPORT)
+                .concat()
+                .try_into()
+                .unwrap(),
+            [
+                0.5, 0.0, 0.0, 0.0, //
+                0.0, 1.0, 0.0, 0.0, //
+                0.0, 0.0, 1.01, 1.0, //
+                0.0, 0.0, -1.01, 0.0,
+            ],
+        );
+
+        let view_projection = camera.view_projection_matrix(VIEWPORT);
+        let [x, y, z, w] = transform(view_projection, [1.0, 2.0, 4.0]);
+        assert_close([x / w, y / w, z / w], [0.0, 0.0, 0.0]);
+        let [x, y, z, w] = transform(view_projection, [203.0, 103.0, 104.0]);
+        assert_close([x / w, y / w, z / w], [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn left_handed() {
+        let camera = camera([2.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_close(camera.right, [0.0, 0.0, -1.0]);
+        assert_close(camera.up, [0.0, 1.0, 0.0]);
+        assert_close(camera.to_view([11.0, 3.0, 1.0]), [2.0, 1.0, 10.0]);
+    }
+
+    #[test]
+    fn project_and_ray() {
+        let camera = camera([1.0, -1.0, 1.0], [0.0, 1.0, 0.0]);
+        let point = [10.0, -2.0, 20.0];
+        let projection = camera.project(point, VIEWPORT);
+        assert!(projection.is_visible());
+
+        let ray = camera.ray(projection.screen, VIEWPORT);
+        let distance = Position::new(camera.position, [0.0; 3], [0.0; 3]).distance_to(point);
+        assert_close(ray.at(distance), point);
+
+        let behind = camera.project([1.0, 3.0, 2.0], VIEWPORT);
+        assert!(behind.behind);
+        assert!(!behind.is_visible());
+    }
+
+    #[test]
+    fn zero_vectors() {
+        let zero = camera([0.0; 3], [0.0; 3]);
+        assert_eq!(zero.forward, [0.0, 0.0, 1.0]);
+        assert_eq!(zero.right, [1.0, 0.0, 0.0]);
+        assert_eq!(zero.up, [0.0, 1.0, 0.0]);
+
+        let unset = Camera::new(&LinkedMem::default().camera, FRAC_PI_2);
+        assert!(unset
+            .view_projection_matrix(VIEWPORT)
+            .iter()
+            .flatten()
+            .all(|value| value.is_finite()));
+
+        let parallel = camera([0.0, 2.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_eq!(parallel.forward, [0.0, 1.0, 0.0]);
+        assert_eq!(parallel.right, [1.0, 0.0, 0.0]);
+        assert_eq!(parallel.up, [0.0, 0.0, -1.0]);
+    }
+}
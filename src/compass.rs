@@ -0,0 +1,267 @@
+use crate::{Context, Continent, UIScaling, UiState};
+
+/// Screen rectangle in pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rect {
+    /// Left edge.
+    pub x: f32,
+
+    /// Top edge.
+    pub y: f32,
+
+    /// Width.
+    pub width: f32,
+
+    /// Height.
+    pub height: f32,
+}
+
+impl Rect {
+    /// Returns the center of the rectangle.
+    #[inline]
+    pub fn center(&self) -> [f32; 2] {
+        [self.x + 0.5 * self.width, self.y + 0.5 * self.height]
+    }
+
+    /// Returns whether the point is inside the rectangle.
+    #[inline]
+    pub fn contains(&self, point: [f32; 2]) -> bool {
+        (self.x..=self.x + self.width).contains(&point[0])
+            && (self.y..=self.y + self.height).contains(&point[1])
+    }
+}
+
+/// Minimap or big map on the screen for drawing markers.
+///
+/// Describes the big map if [`UiState::IS_MAP_OPEN`] is set, otherwise the minimap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Compass {
+    /// Screen rectangle of the map in pixels.
+    pub rect: Rect,
+
+    /// Continent coordinates at the center of the map.
+    pub center: Continent,
+
+    /// Screen pixels per continent unit.
+    pub scale: f32,
+
+    /// Rotation of the map in radians.
+    ///
+    /// Always `0` for the big map and the minimap without rotation enabled.
+    pub rotation: f32,
+
+    /// Whether this is the big map.
+    pub is_map_open: bool,
+}
+
+impl Compass {
+    /// Creates the compass from the [`Context`], screen size in pixels and [`UIScaling`].
+    pub fn new(context: &Context, screen: [f32; 2], ui_scale: UIScaling) -> Self {
+        let [screen_width, screen_height] = screen;
+        let is_map_open = context.ui_state.contains(UiState::IS_MAP_OPEN);
+        let rect = if is_map_open {
+            Rect {
+                x: 0.0,
+                y: 0.0,
+                width: screen_width,
+                height: screen_height,
+            }
+        } else {
+            let width = context.compass_width.into();
+            let height = context.compass_height.into();
+            let y = if context.ui_state.contains(UiState::IS_COMPASS_TOP_RIGHT) {
+                0.0
+            } else {
+                screen_height - height - Self::bottom_offset(ui_scale)
+            };
+            Rect {
+                x: screen_width - width,
+                y,
+                width,
+                height,
+            }
+        };
+        let rotation = if !is_map_open
+            && context
+                .ui_state
+                .contains(UiState::DOES_COMPASS_HAVE_ROTATION_ENABLED)
+        {
+            context.compass_rotation
+        } else {
+            0.0
+        };
+        Self {
+            rect,
+            center: context.map_center_continent(),
+            scale: Self::scale_factor(ui_scale) / context.map_scale,
+            rotation,
+            is_map_open,
+        }
+    }
+
+    /// Returns the size factor of the [`UIScaling`] relative to [`UIScaling::Normal`].
+    ///
+    /// The factors are approximations measured from in-game screenshots, not values published by the game.
+    pub fn scale_factor(ui_scale: UIScaling) -> f32 {
+        match ui_scale {
+            UIScaling::Small => 0.903,
+            UIScaling::Normal | UIScaling::Unknown(_) => 1.0,
+            UIScaling::Large => 1.115,
+            UIScaling::Larger => 1.23,
+        }
+    }
+
+    /// Returns the distance in pixels between the bottom of the minimap and the bottom of the screen.
+    ///
+    /// The offsets are approximations measured from in-game screenshots, not values published by the game.
+    pub fn bottom_offset(ui_scale: UIScaling) -> f32 {
+        match ui_scale {
+            UIScaling::Small => 33.0,
+            UIScaling::Normal | UIScaling::Unknown(_) => 37.0,
+            UIScaling::Large => 41.0,
+            UIScaling::Larger => 45.0,
+        }
+    }
+
+    /// Maps a continent coordinate to a screen pixel without clipping.
+    pub fn to_screen(&self, point: Continent) -> [f32; 2] {
+        let x = (point.0[0] - self.center.0[0]) * self.scale;
+        let y = (point.0[1] - self.center.0[1]) * self.scale;
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let [center_x, center_y] = self.rect.center();
+        [center_x + x * cos - y * sin, center_y + x * sin + y * cos]
+    }
+
+    /// Maps a continent coordinate to a screen pixel.
+    ///
+    /// Returns [`None`] for points outside of the map.
+    #[inline]
+    pub fn project(&self, point: Continent) -> Option<[f32; 2]> {
+        let pixel = self.to_screen(point);
+        self.rect.contains(pixel).then_some(pixel)
+    }
+
+    /// Maps a screen pixel back to a continent coordinate.
+    pub fn to_continent(&self, pixel: [f32; 2]) -> Continent {
+        let [center_x, center_y] = self.rect.center();
+        let (x, y) = (pixel[0] - center_x, pixel[1] - center_y);
+        let (sin, cos) = self.rotation.sin_cos();
+        Continent([
+            self.center.0[0] + (x * cos - y * sin) / self.scale,
+            self.center.0[1] + (x * sin + y * cos) / self.scale,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(ui_state: UiState) -> Context {
+        Context {
+            ui_state,
+            compass_width: 300,
+            compass_height: 200,
+            compass_rotation: 1.25,
+            map_center_x: 1000.0,
+            map_center_y: 2000.0,
+            map_scale: 2.0,
+            ..Context::default()
+        }
+    }
+
+    fn assert_close(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-3 && (actual[1] - expected[1]).abs() < 1e-3,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn minimap_bottom() {
+        let compass = Compass::new(
+            &context(UiState::empty()),
+            [1920.0, 1080.0],
+            UIScaling::Large,
+        );
+        assert_eq!(
+            compass.rect,
+            Rect {
+                x: 1620.0,
+                y: 1080.0 - 200.0 - 41.0,
+                width: 300.0,
+                height: 200.0,
+            }
+        );
+        assert_eq!(compass.scale, 1.115 / 2.0);
+        assert_eq!(compass.rotation, 0.0);
+        assert!(!compass.is_map_open);
+        assert_eq!(compass.project(compass.center), Some(compass.rect.center()));
+    }
+
+    #[test]
+    fn minimap_top_right() {
+        let compass = Compass::new(
+            &context(UiState::IS_COMPASS_TOP_RIGHT | UiState::DOES_COMPASS_HAVE_ROTATION_ENABLED),
+            [1920.0, 1080.0],
+            UIScaling::Normal,
+        );
+        assert_eq!(
+            compass.rect,
+            Rect {
+                x: 1620.0,
+                y: 0.0,
+                width: 300.0,
+                height: 200.0,
+            }
+        );
+        assert_eq!(compass.rotation, 1.25);
+        assert_eq!(compass.project(Continent([5000.0, 2000.0])), None);
+    }
+
+    #[test]
+    fn map_open() {
+        let compass = Compass::new(
+            &context(UiState::IS_MAP_OPEN | UiState::DOES_COMPASS_HAVE_ROTATION_ENABLED),
+            [1920.0, 1080.0],
+            UIScaling::Normal,
+        );
+        assert_eq!(
+            compass.rect,
+            Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 1920.0,
+                height: 1080.0,
+            }
+        );
+        assert_eq!(compass.rotation, 0.0);
+        assert!(compass.is_map_open);
+        assert_close(
+            compass.to_screen(Continent([1100.0, 1900.0])),
+            [1010.0, 490.0],
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let compass = Compass::new(
+            &context(UiState::DOES_COMPASS_HAVE_ROTATION_ENABLED),
+            [1920.0, 1080.0],
+            UIScaling::Small,
+        );
+        for point in [[1000.0, 2000.0], [1050.0, 1980.0], [900.0, 2100.0]] {
+            let pixel = compass.to_screen(Continent(point));
+            assert_close(compass.to_continent(pixel).0, point);
+        }
+
+        // rotation is applied counter clockwise to the north-up offset
+        let [center_x, center_y] = compass.rect.center();
+        let offset = 10.0 * compass.scale;
+        let (sin, cos) = 1.25f32.sin_cos();
+        assert_close(
+            compass.to_screen(Continent([1010.0, 2000.0])),
+            [center_x + offset * cos, center_y - offset * sin],
+        );
+    }
+}
@@ -1,12 +1,22 @@
+use crate::{
+    decode_sockaddr, encode_sockaddr,
+    util::{display_unknown, serde_primitive},
+    MapType,
+};
 use bitflags::bitflags;
+use std::net::SocketAddr;
 
 /// MumbleLink context specific to Guild Wars 2.
-#[derive(Debug, Clone)]
-#[repr(C)]
+///
+/// Typed version of [`RawContext`].
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Context {
     /// Address of the server.
     ///
     /// Contains `socketaddr_in` or `socketaddr_in6`.
+    /// See [`decode_sockaddr`](crate::decode_sockaddr) for decoding.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::sockaddr"))]
     pub server_address: [u8; 28],
 
     /// Id of the current map.
@@ -14,8 +24,8 @@ pub struct Context {
 
     /// Type of the current map.
     ///
-    /// See the [`map_type`] module for some known types.
-    pub map_type: u32,
+    /// See [`MapType`] for known types.
+    pub map_type: MapType,
 
     /// Current shard id.
     pub shard_id: u32,
@@ -68,9 +78,178 @@ pub struct Context {
     pub mount_index: Mount,
 }
 
+/// Raw MumbleLink context specific to Guild Wars 2 as laid out in memory.
+///
+/// Holds enum values as raw integers, any bit pattern written by the game is valid.
+/// Use [`Context`] for the typed version.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct RawContext {
+    /// Address of the server.
+    ///
+    /// Contains `socketaddr_in` or `socketaddr_in6`.
+    /// See [`decode_sockaddr`](crate::decode_sockaddr) for decoding.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::sockaddr"))]
+    pub server_address: [u8; 28],
+
+    /// Id of the current map.
+    pub map_id: u32,
+
+    /// Type of the current map.
+    ///
+    /// See [`MapType`] and the [`map_type`](crate::map_type) module for known types.
+    pub map_type: u32,
+
+    /// Current shard id.
+    pub shard_id: u32,
+
+    /// Current instance id.
+    pub instance: u32,
+
+    /// Current build id.
+    pub build_id: u32,
+
+    /// Current UI state bitflags.
+    ///
+    /// See [`UiState`].
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::ui_state"))]
+    pub ui_state: u32,
+
+    /// Compass width in pixels.
+    pub compass_width: u16,
+
+    /// Compass height in pixels.
+    pub compass_height: u16,
+
+    /// Compass rotation in radians.
+    pub compass_rotation: f32,
+
+    /// Player position x in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    pub player_x: f32,
+
+    /// Player position y in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    pub player_y: f32,
+
+    /// Map center x in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    pub map_center_x: f32,
+
+    /// Map center y in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    pub map_center_y: f32,
+
+    /// Map scale.
+    pub map_scale: f32,
+
+    /// Process id.
+    pub process_id: u32,
+
+    /// Currently used mount.
+    ///
+    /// See [`Mount`].
+    pub mount_index: u8,
+}
+
+impl Context {
+    /// Returns the decoded server address.
+    #[inline]
+    pub fn server_socket_addr(&self) -> Option<SocketAddr> {
+        decode_sockaddr(&self.server_address)
+    }
+
+    /// Sets the server address from a [`SocketAddr`].
+    #[inline]
+    pub fn set_server_socket_addr(&mut self, addr: &SocketAddr) {
+        self.server_address = encode_sockaddr(addr);
+    }
+}
+
+impl RawContext {
+    /// Returns the decoded server address.
+    #[inline]
+    pub fn server_socket_addr(&self) -> Option<SocketAddr> {
+        decode_sockaddr(&self.server_address)
+    }
+
+    /// Sets the server address from a [`SocketAddr`].
+    #[inline]
+    pub fn set_server_socket_addr(&mut self, addr: &SocketAddr) {
+        self.server_address = encode_sockaddr(addr);
+    }
+}
+
+impl From<RawContext> for Context {
+    #[inline]
+    fn from(raw: RawContext) -> Self {
+        Self::from(&raw)
+    }
+}
+
+impl From<&RawContext> for Context {
+    fn from(raw: &RawContext) -> Self {
+        Self {
+            server_address: raw.server_address,
+            map_id: raw.map_id,
+            map_type: MapType::from(raw.map_type),
+            shard_id: raw.shard_id,
+            instance: raw.instance,
+            build_id: raw.build_id,
+            ui_state: UiState::from_bits_retain(raw.ui_state),
+            compass_width: raw.compass_width,
+            compass_height: raw.compass_height,
+            compass_rotation: raw.compass_rotation,
+            player_x: raw.player_x,
+            player_y: raw.player_y,
+            map_center_x: raw.map_center_x,
+            map_center_y: raw.map_center_y,
+            map_scale: raw.map_scale,
+            process_id: raw.process_id,
+            mount_index: Mount::from(raw.mount_index),
+        }
+    }
+}
+
+impl From<Context> for RawContext {
+    #[inline]
+    fn from(context: Context) -> Self {
+        Self::from(&context)
+    }
+}
+
+impl From<&Context> for RawContext {
+    fn from(context: &Context) -> Self {
+        Self {
+            server_address: context.server_address,
+            map_id: context.map_id,
+            map_type: context.map_type.into(),
+            shard_id: context.shard_id,
+            instance: context.instance,
+            build_id: context.build_id,
+            ui_state: context.ui_state.bits(),
+            compass_width: context.compass_width,
+            compass_height: context.compass_height,
+            compass_rotation: context.compass_rotation,
+            player_x: context.player_x,
+            player_y: context.player_y,
+            map_center_x: context.map_center_x,
+            map_center_y: context.map_center_y,
+            map_scale: context.map_scale,
+            process_id: context.process_id,
+            mount_index: context.mount_index.into(),
+        }
+    }
+}
+
 bitflags! {
     /// Current UI state.
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     #[repr(C)]
     pub struct UiState: u32 {
         const IS_MAP_OPEN = 0b1;
@@ -89,6 +268,80 @@ bitflags! {
     }
 }
 
+/// Serializes the [`UiState`] as list of flag names.
+///
+/// Unknown bits are appended as hexadecimal string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UiState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        if !serializer.is_human_readable() {
+            return self.bits().serialize(serializer);
+        }
+        let unknown = self.bits() & !Self::all().bits();
+        let len = self.iter_names().count() + usize::from(unknown != 0);
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for (name, _) in self.iter_names() {
+            seq.serialize_element(name)?;
+        }
+        if unknown != 0 {
+            seq.serialize_element(&format!("{unknown:#x}"))?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UiState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_seq(UiStateVisitor)
+        } else {
+            u32::deserialize(deserializer).map(Self::from_bits_retain)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct UiStateVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for UiStateVisitor {
+    type Value = UiState;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a list of ui state flag names")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        use serde::de::{Error, Unexpected};
+
+        let mut state = Self::Value::empty();
+        while let Some(name) = seq.next_element::<std::borrow::Cow<'de, str>>()? {
+            let flag = Self::Value::from_name(&name)
+                .or_else(|| {
+                    let hex = name.strip_prefix("0x")?;
+                    u32::from_str_radix(hex, 16)
+                        .ok()
+                        .map(Self::Value::from_bits_retain)
+                })
+                .ok_or_else(|| A::Error::invalid_value(Unexpected::Str(&name), &self))?;
+            state |= flag;
+        }
+        Ok(state)
+    }
+}
+
 /// Mount.
 #[derive(
     Debug,
@@ -99,9 +352,8 @@ bitflags! {
     PartialOrd,
     Ord,
     Hash,
-    strum::Display,
     strum::AsRefStr,
-    num_enum::TryFromPrimitive,
+    num_enum::FromPrimitive,
     num_enum::IntoPrimitive,
 )]
 #[repr(u8)]
@@ -117,4 +369,43 @@ pub enum Mount {
     Skyscale = 8,
     Skiff = 9,
     SiegeTurtle = 10,
+
+    /// Mount unknown to this version of the crate.
+    #[num_enum(catch_all)]
+    Unknown(u8),
+}
+
+// num_enum treats #[default] as fallback variant, which conflicts with the catch-all `Unknown`
+impl Default for Mount {
+    #[inline]
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+serde_primitive!(Mount => u8);
+display_unknown!(Mount);
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ui_state_json() {
+        for (state, json) in [
+            (UiState::empty(), "[]"),
+            (
+                UiState::IS_COMPASS_TOP_RIGHT | UiState::IS_IN_COMBAT,
+                r#"["IS_COMPASS_TOP_RIGHT","IS_IN_COMBAT"]"#,
+            ),
+            (
+                UiState::from_bits_retain(0b1 | 0xff00),
+                r#"["IS_MAP_OPEN","0xff00"]"#,
+            ),
+        ] {
+            assert_eq!(serde_json::to_string(&state).unwrap(), json);
+            assert_eq!(serde_json::from_str::<UiState>(json).unwrap(), state);
+        }
+        assert!(serde_json::from_str::<UiState>(r#"["IS_FLYING"]"#).is_err());
+    }
 }
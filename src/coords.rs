@@ -0,0 +1,234 @@
+use crate::{Context, Position, RawContext};
+
+/// Number of inches per meter.
+pub const INCHES_PER_METER: f32 = 39.3701;
+
+/// Position in map coordinates in meters.
+///
+/// Used by the [`LinkedMem`](crate::LinkedMem) `avatar` and `camera` positions.
+/// Holds `x`, `y` and `z` with `y` pointing up.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MapMeters(pub [f32; 3]);
+
+/// Position in map coordinates in inches.
+///
+/// Used by the `map_rect` of [API:2/maps](https://wiki.guildwars2.com/wiki/API:2/maps).
+/// Holds `x`, `y` and `z` with `y` pointing up.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MapInches(pub [f32; 3]);
+
+/// Position in continent coordinates.
+///
+/// Used by the [`Context`] player and map center positions and the `continent_rect` of [API:2/maps](https://wiki.guildwars2.com/wiki/API:2/maps).
+/// Holds `x` and `y` with `y` pointing down.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Continent(pub [f32; 2]);
+
+/// Bounds of a map for conversions between map and continent coordinates.
+///
+/// See [API:1/event_details#Coordinate_recalculation](https://wiki.guildwars2.com/wiki/API:1/event_details#Coordinate_recalculation).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MapBounds {
+    /// Map rectangle in map inches as `[[min_x, min_y], [max_x, max_y]]`.
+    ///
+    /// The `y` of the map rectangle corresponds to the horizontal `z` of map positions.
+    pub map_rect: [[f32; 2]; 2],
+
+    /// Continent rectangle in continent coordinates as `[[min_x, min_y], [max_x, max_y]]`.
+    pub continent_rect: [[f32; 2]; 2],
+}
+
+impl MapBounds {
+    /// Creates new bounds from the `map_rect` and `continent_rect` of a map.
+    #[inline]
+    pub const fn new(map_rect: [[f32; 2]; 2], continent_rect: [[f32; 2]; 2]) -> Self {
+        Self {
+            map_rect,
+            continent_rect,
+        }
+    }
+}
+
+impl MapMeters {
+    /// Converts to map inches.
+    #[inline]
+    pub fn to_inches(self) -> MapInches {
+        self.into()
+    }
+
+    /// Converts to continent coordinates using the given map bounds.
+    #[inline]
+    pub fn to_continent(self, bounds: &MapBounds) -> Continent {
+        self.to_inches().to_continent(bounds)
+    }
+}
+
+impl MapInches {
+    /// Converts to map meters.
+    #[inline]
+    pub fn to_meters(self) -> MapMeters {
+        self.into()
+    }
+
+    /// Converts to continent coordinates using the given map bounds.
+    ///
+    /// The height is discarded.
+    pub fn to_continent(self, bounds: &MapBounds) -> Continent {
+        let [x, _, z] = self.0;
+        let [[map_x0, map_y0], [map_x1, map_y1]] = bounds.map_rect;
+        let [[cont_x0, cont_y0], [cont_x1, cont_y1]] = bounds.continent_rect;
+        Continent([
+            cont_x0 + (cont_x1 - cont_x0) * (x - map_x0) / (map_x1 - map_x0),
+            cont_y0 + (cont_y1 - cont_y0) * (1.0 - (z - map_y0) / (map_y1 - map_y0)),
+        ])
+    }
+}
+
+impl Continent {
+    /// Converts to map inches using the given map bounds and height in inches.
+    pub fn to_map_inches(self, bounds: &MapBounds, height: f32) -> MapInches {
+        let [x, y] = self.0;
+        let [[map_x0, map_y0], [map_x1, map_y1]] = bounds.map_rect;
+        let [[cont_x0, cont_y0], [cont_x1, cont_y1]] = bounds.continent_rect;
+        MapInches([
+            map_x0 + (map_x1 - map_x0) * (x - cont_x0) / (cont_x1 - cont_x0),
+            height,
+            map_y0 + (map_y1 - map_y0) * (1.0 - (y - cont_y0) / (cont_y1 - cont_y0)),
+        ])
+    }
+
+    /// Converts to map meters using the given map bounds and height in meters.
+    #[inline]
+    pub fn to_map_meters(self, bounds: &MapBounds, height: f32) -> MapMeters {
+        self.to_map_inches(bounds, height * INCHES_PER_METER)
+            .to_meters()
+    }
+}
+
+impl From<MapInches> for MapMeters {
+    #[inline]
+    fn from(inches: MapInches) -> Self {
+        Self(inches.0.map(|value| value / INCHES_PER_METER))
+    }
+}
+
+impl From<MapMeters> for MapInches {
+    #[inline]
+    fn from(meters: MapMeters) -> Self {
+        Self(meters.0.map(|value| value * INCHES_PER_METER))
+    }
+}
+
+impl From<[f32; 3]> for MapMeters {
+    #[inline]
+    fn from(position: [f32; 3]) -> Self {
+        Self(position)
+    }
+}
+
+impl From<[f32; 3]> for MapInches {
+    #[inline]
+    fn from(position: [f32; 3]) -> Self {
+        Self(position)
+    }
+}
+
+impl From<[f32; 2]> for Continent {
+    #[inline]
+    fn from(position: [f32; 2]) -> Self {
+        Self(position)
+    }
+}
+
+impl Position {
+    /// Returns the position in map meters.
+    #[inline]
+    pub fn map_meters(&self) -> MapMeters {
+        MapMeters(self.position)
+    }
+}
+
+impl Context {
+    /// Returns the player position in continent coordinates.
+    #[inline]
+    pub fn player_continent(&self) -> Continent {
+        Continent([self.player_x, self.player_y])
+    }
+
+    /// Returns the map center in continent coordinates.
+    #[inline]
+    pub fn map_center_continent(&self) -> Continent {
+        Continent([self.map_center_x, self.map_center_y])
+    }
+}
+
+impl RawContext {
+    /// Returns the player position in continent coordinates.
+    #[inline]
+    pub fn player_continent(&self) -> Continent {
+        Continent([self.player_x, self.player_y])
+    }
+
+    /// Returns the map center in continent coordinates.
+    #[inline]
+    pub fn map_center_continent(&self) -> Continent {
+        Continent([self.map_center_x, self.map_center_y])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bounds of Queensdale (map id 15) from API:2/maps.
+    const QUEENSDALE: MapBounds = MapBounds::new(
+        [[-43008.0, -27648.0], [43008.0, 30720.0]],
+        [[9856.0, 11648.0], [13440.0, 14080.0]],
+    );
+
+    fn assert_close<const N: usize>(actual: [f32; N], expected: [f32; N], epsilon: f32) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < epsilon),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn continent() {
+        for (inches, continent) in [
+            ([-43008.0, 0.0, -27648.0], [9856.0, 14080.0]),
+            ([43008.0, 0.0, 30720.0], [13440.0, 11648.0]),
+            ([-43008.0, 0.0, 30720.0], [9856.0, 11648.0]),
+            ([0.0, 0.0, 0.0], [11648.0, 12928.0]),
+            ([2400.0, 100.0, 2400.0], [11748.0, 12828.0]),
+        ] {
+            let inches = MapInches(inches);
+            assert_eq!(inches.to_continent(&QUEENSDALE), Continent(continent));
+            assert_close(
+                Continent(continent)
+                    .to_map_inches(&QUEENSDALE, inches.0[1])
+                    .0,
+                inches.0,
+                1e-2,
+            );
+        }
+    }
+
+    #[test]
+    fn meters() {
+        let meters = MapMeters([-321.5, 42.0, 123.25]);
+        assert_close(meters.to_inches().to_meters().0, meters.0, 1e-4);
+        assert_close(
+            meters.to_inches().0,
+            [-12657.487, 1653.5442, 4852.3647],
+            1e-2,
+        );
+
+        let continent = meters.to_continent(&QUEENSDALE);
+        assert_close(continent.0, [11120.605, 12725.818], 1e-2);
+        assert_close(continent.to_map_meters(&QUEENSDALE, 42.0).0, meters.0, 1e-2);
+    }
+}
@@ -3,7 +3,7 @@ use thiserror::Error;
 
 /// A possible error occurring during [`MumbleLink`](crate::MumbleLink) creation.
 #[derive(Debug, Error)]
-#[cfg(windows)]
+#[cfg(any(windows, unix))]
 pub enum Error {
     /// MumbleLink is disabled.
     #[error("mumblelink disabled")]
@@ -13,9 +13,81 @@ pub enum Error {
     #[error(transparent)]
     NulError(#[from] NulError),
 
+    #[cfg(windows)]
     #[error(transparent)]
     WinError(#[from] windows::core::Error),
 
+    /// Shared memory object or file is smaller than [`LinkedMem`](crate::LinkedMem).
+    #[cfg(unix)]
+    #[error("mumblelink too small: {0} bytes")]
+    TooSmall(u64),
+
+    #[error(transparent)]
+    IoError(#[from] io::Error),
+}
+
+/// An error occurring when decoding UTF-16 wide chars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+#[error("invalid utf-16 code unit {unit:#06x} at offset {offset}")]
+pub struct DecodeError {
+    /// Offset of the invalid code unit.
+    pub offset: usize,
+
+    /// Invalid code unit.
+    pub unit: u16,
+}
+
+/// An error occurring when exceeding the capacity of a [`CharacterName`](crate::CharacterName).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+#[error("name exceeds capacity")]
+pub struct CapacityError;
+
+/// An error occurring when parsing the identity JSON via [`Identity::from_wide`](crate::Identity::from_wide).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error)]
+pub enum IdentityError {
+    /// Invalid JSON at the given code unit offset.
+    #[error("invalid identity json at offset {0}")]
+    Syntax(usize),
+
+    /// Field is missing.
+    #[error("missing identity field {0}")]
+    MissingField(&'static str),
+
+    /// Field has an invalid value.
+    #[error("invalid value for identity field {0}")]
+    InvalidField(&'static str),
+}
+
+/// An error occurring when reading a recording via [`Reader`](crate::recording::Reader).
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    /// Data is not a recording.
+    #[error("invalid recording header")]
+    InvalidHeader,
+
+    /// Recording format version is not supported.
+    #[error("unsupported recording format version {0}")]
+    UnsupportedVersion(u16),
+
+    /// Frame has invalid contents.
+    #[error("invalid recording frame")]
+    InvalidFrame,
+
+    #[error(transparent)]
+    IoError(#[from] io::Error),
+}
+
+/// An error occurring when reading a [`Trail`](crate::trail::Trail).
+#[derive(Debug, Error)]
+pub enum TrailError {
+    /// Trail format version is not supported.
+    #[error("unsupported trail format version {0}")]
+    UnsupportedVersion(u32),
+
+    /// Point data length in bytes is not a multiple of a point.
+    #[error("invalid trail data length {0}")]
+    InvalidLength(usize),
+
     #[error(transparent)]
     IoError(#[from] io::Error),
 }
@@ -0,0 +1,248 @@
+use crate::{map_type, until_nul, LinkSource, LinkedMem, Liveness, LivenessState};
+use std::time::Instant;
+
+/// State of the game derived from successive [`LinkedMem`] reads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameState {
+    /// The game is not running or the MumbleLink contents are stale.
+    #[default]
+    NotRunning,
+
+    /// The player is at character select or character creation.
+    CharacterSelect,
+
+    /// The game is on a loading screen.
+    ///
+    /// `from_map` is `0` when loading from character select.
+    /// `to_map` equals `from_map` until the new map id is known or if the map did not change.
+    Loading { from_map: u32, to_map: u32 },
+
+    /// The player is in game on the given map.
+    InGame { map_id: u32 },
+}
+
+impl GameState {
+    /// Returns whether the player is in game.
+    #[inline]
+    pub fn is_in_game(&self) -> bool {
+        matches!(self, Self::InGame { .. })
+    }
+
+    /// Returns whether the game is on a loading screen.
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading { .. })
+    }
+
+    /// Returns the current map id, if in game.
+    #[inline]
+    pub fn map_id(&self) -> Option<u32> {
+        match *self {
+            Self::InGame { map_id } => Some(map_id),
+            _ => None,
+        }
+    }
+}
+
+/// State machine deriving the [`GameState`] from successive [`LinkedMem`] reads.
+///
+/// Loading screens are detected from `map_id` transitions and `ui_tick` stalls reported by the [`Liveness`] tracker.
+/// A stall on the same map, for example a frozen game, is reported as [`GameState::Loading`] from and to that map.
+/// Character select is detected from an empty identity or the [`map_type::CHARACTER_CREATION`] map type.
+/// Stale contents stay [`GameState::NotRunning`] until the `ui_tick` advances.
+#[derive(Debug, Clone)]
+pub struct GameStateMachine {
+    liveness: Liveness,
+    state: GameState,
+    map_id: u32,
+    loading_tick: Option<u32>,
+}
+
+impl GameStateMachine {
+    /// Creates a new state machine with a default [`Liveness`] tracker.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_liveness(Liveness::new())
+    }
+
+    /// Creates a new state machine with the given [`Liveness`] tracker.
+    #[inline]
+    pub fn with_liveness(liveness: Liveness) -> Self {
+        Self {
+            liveness,
+            state: GameState::NotRunning,
+            map_id: 0,
+            loading_tick: None,
+        }
+    }
+
+    /// Returns the current [`GameState`].
+    #[inline]
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Returns the underlying [`Liveness`] tracker.
+    #[inline]
+    pub fn liveness(&self) -> &Liveness {
+        &self.liveness
+    }
+
+    /// Reads from the source and returns the new [`GameState`] if it changed.
+    #[inline]
+    pub fn poll(&mut self, source: &impl LinkSource) -> Option<GameState> {
+        self.update(&source.read())
+    }
+
+    /// Updates with the given [`LinkedMem`] and returns the new [`GameState`] if it changed.
+    #[inline]
+    pub fn update(&mut self, mem: &LinkedMem) -> Option<GameState> {
+        self.update_at(mem, Instant::now())
+    }
+
+    /// Updates with the given [`LinkedMem`] read at the given time and returns the new [`GameState`] if it changed.
+    pub fn update_at(&mut self, mem: &LinkedMem, now: Instant) -> Option<GameState> {
+        let tick = mem.ui_tick;
+        let map_id = mem.context.map_id;
+
+        let liveness = self.liveness.update_at(mem, now);
+        let next = if !liveness.is_live() || !self.liveness.has_advanced() {
+            self.map_id = 0;
+            self.loading_tick = None;
+            GameState::NotRunning
+        } else if until_nul(&mem.identity).is_empty()
+            || mem.context.map_type == map_type::CHARACTER_CREATION
+        {
+            self.map_id = 0;
+            self.loading_tick = None;
+            GameState::CharacterSelect
+        } else if liveness == LivenessState::Paused {
+            self.loading_tick = Some(tick);
+            self.loading(map_id)
+        } else {
+            match self.loading_tick {
+                Some(loading_tick) if loading_tick == tick => self.loading(map_id),
+                None if map_id != self.map_id
+                    && matches!(
+                        self.state,
+                        GameState::InGame { .. } | GameState::CharacterSelect
+                    ) =>
+                {
+                    self.loading_tick = Some(tick);
+                    self.loading(map_id)
+                }
+                _ => {
+                    self.map_id = map_id;
+                    self.loading_tick = None;
+                    GameState::InGame { map_id }
+                }
+            }
+        };
+
+        (next != self.state).then(|| {
+            self.state = next;
+            next
+        })
+    }
+
+    #[inline]
+    fn loading(&self, to_map: u32) -> GameState {
+        GameState::Loading {
+            from_map: self.map_id,
+            to_map,
+        }
+    }
+}
+
+impl Default for GameStateMachine {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn mem(tick: u32, map_id: u32, in_game: bool) -> LinkedMem {
+        let mut mem = crate::test_util::mem(tick, 42);
+        mem.context.map_id = map_id;
+        if in_game {
+            mem.context.map_type = map_type::PVE;
+            mem.identity[..2].copy_from_slice(&[b'{'.into(), b'}'.into()]);
+        } else {
+            mem.context.map_type = map_type::CHARACTER_CREATION;
+        }
+        mem
+    }
+
+    #[test]
+    fn stale() {
+        let mut machine =
+            GameStateMachine::with_liveness(Liveness::new().with_process_check(false));
+        let start = Instant::now();
+        let stale = mem(1234, 15, true);
+        for millis in [0, 100, 600, 30_000, 61_000] {
+            let now = start + Duration::from_millis(millis);
+            assert_eq!(machine.update_at(&stale, now), None, "at {millis}ms");
+            assert_eq!(machine.state(), GameState::NotRunning);
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let mut machine =
+            GameStateMachine::with_liveness(Liveness::new().with_process_check(false));
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        assert_eq!(machine.update_at(&mem(1, 0, false), at(0)), None);
+        assert_eq!(
+            machine.update_at(&mem(2, 0, false), at(20)),
+            Some(GameState::CharacterSelect)
+        );
+        assert_eq!(
+            machine.update_at(&mem(3, 15, true), at(40)),
+            Some(GameState::Loading {
+                from_map: 0,
+                to_map: 15
+            })
+        );
+        assert_eq!(
+            machine.update_at(&mem(4, 15, true), at(60)),
+            Some(GameState::InGame { map_id: 15 })
+        );
+        assert_eq!(machine.update_at(&mem(5, 15, true), at(80)), None);
+        assert_eq!(
+            machine.update_at(&mem(6, 50, true), at(100)),
+            Some(GameState::Loading {
+                from_map: 15,
+                to_map: 50
+            })
+        );
+        assert_eq!(machine.update_at(&mem(6, 50, true), at(300)), None);
+        assert_eq!(machine.update_at(&mem(6, 50, true), at(5_000)), None);
+        assert_eq!(
+            machine.update_at(&mem(7, 50, true), at(5_020)),
+            Some(GameState::InGame { map_id: 50 })
+        );
+        assert_eq!(
+            machine.update_at(&mem(7, 50, true), at(6_000)),
+            Some(GameState::Loading {
+                from_map: 50,
+                to_map: 50
+            })
+        );
+        assert_eq!(
+            machine.update_at(&mem(8, 50, true), at(6_020)),
+            Some(GameState::InGame { map_id: 50 })
+        );
+        assert_eq!(
+            machine.update_at(&mem(8, 50, true), at(90_000)),
+            Some(GameState::NotRunning)
+        );
+    }
+}
@@ -1,12 +1,17 @@
+use crate::{
+    util::{display_unknown, serde_primitive},
+    CapacityError,
+};
+use std::fmt;
+
 /// Information about player identity.
 ///
-/// Parsed as JSON from the `identity` field in [`LinkedMem`].
+/// Parsed as JSON from the `identity` field in [`LinkedMem`](crate::LinkedMem).
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-
 pub struct Identity {
     /// Character name.
-    pub name: String,
+    pub name: CharacterName,
 
     /// Character profession.
     pub profession: Profession,
@@ -25,7 +30,7 @@ pub struct Identity {
     /// See [API:2/maps](https://wiki.guildwars2.com/wiki/API:2/maps).
     pub map_id: u32,
 
-    /// Shard id from [`Context`].
+    /// Shard id from [`Context`](crate::Context).
     ///
     /// Formerly character homeworld per [API:2/worlds](https://wiki.guildwars2.com/wiki/API:2/worlds).
     /// Not usable since the switch to the megaserver system.
@@ -47,6 +52,136 @@ pub struct Identity {
     pub ui_scale: UIScaling,
 }
 
+/// Character name stored inline with a fixed capacity.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharacterName {
+    len: u8,
+    bytes: [u8; Self::CAPACITY],
+}
+
+impl CharacterName {
+    /// Maximum length of the UTF-8 encoded name in bytes.
+    pub const CAPACITY: usize = 64;
+
+    /// Creates a new empty name.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; Self::CAPACITY],
+        }
+    }
+
+    /// Returns the name as string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // only ever filled from valid strings and chars
+        std::str::from_utf8(&self.bytes[..self.len as usize]).expect("invalid utf-8 in name")
+    }
+
+    /// Appends a [`char`] to the name.
+    ///
+    /// Fails if the capacity is exceeded.
+    pub fn push(&mut self, char: char) -> Result<(), CapacityError> {
+        let len = self.len as usize;
+        let char_len = char.len_utf8();
+        if len + char_len > Self::CAPACITY {
+            return Err(CapacityError);
+        }
+        char.encode_utf8(&mut self.bytes[len..]);
+        self.len += char_len as u8;
+        Ok(())
+    }
+}
+
+impl Default for CharacterName {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&str> for CharacterName {
+    type Error = CapacityError;
+
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        let mut name = Self::new();
+        let bytes = string.as_bytes();
+        name.bytes
+            .get_mut(..bytes.len())
+            .ok_or(CapacityError)?
+            .copy_from_slice(bytes);
+        name.len = bytes.len() as u8;
+        Ok(name)
+    }
+}
+
+impl std::ops::Deref for CharacterName {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for CharacterName {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for CharacterName {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CharacterName {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for CharacterName {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for CharacterName {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CharacterName {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CharacterName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let string = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        Self::try_from(string.as_ref()).map_err(serde::de::Error::custom)
+    }
+}
+
 /// Character profession.
 #[derive(
     Debug,
@@ -57,15 +192,11 @@ pub struct Identity {
     PartialOrd,
     Ord,
     Hash,
-    strum::Display,
     strum::AsRefStr,
-    num_enum::TryFromPrimitive,
+    strum::IntoStaticStr,
+    num_enum::FromPrimitive,
     num_enum::IntoPrimitive,
 )]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
-)]
 #[repr(u8)]
 pub enum Profession {
     Guardian = 1,
@@ -77,8 +208,15 @@ pub enum Profession {
     Mesmer = 7,
     Necromancer = 8,
     Revenant = 9,
+
+    /// Profession unknown to this version of the crate.
+    #[num_enum(catch_all)]
+    Unknown(u8),
 }
 
+serde_primitive!(Profession => u8);
+display_unknown!(Profession);
+
 /// Character race.
 #[derive(
     Debug,
@@ -89,15 +227,10 @@ pub enum Profession {
     PartialOrd,
     Ord,
     Hash,
-    strum::Display,
     strum::AsRefStr,
-    num_enum::TryFromPrimitive,
+    num_enum::FromPrimitive,
     num_enum::IntoPrimitive,
 )]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
-)]
 #[repr(u8)]
 pub enum Race {
     Asura = 0,
@@ -105,8 +238,15 @@ pub enum Race {
     Human = 2,
     Norn = 3,
     Sylvari = 4,
+
+    /// Race unknown to this version of the crate.
+    #[num_enum(catch_all)]
+    Unknown(u8),
 }
 
+serde_primitive!(Race => u8);
+display_unknown!(Race);
+
 /// User UI scaling.
 #[derive(
     Debug,
@@ -117,19 +257,48 @@ pub enum Race {
     PartialOrd,
     Ord,
     Hash,
-    strum::Display,
     strum::AsRefStr,
-    num_enum::TryFromPrimitive,
+    num_enum::FromPrimitive,
     num_enum::IntoPrimitive,
 )]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr)
-)]
 #[repr(u8)]
 pub enum UIScaling {
     Small = 0,
     Normal = 1,
     Large = 2,
     Larger = 3,
+
+    /// UI scaling unknown to this version of the crate.
+    #[num_enum(catch_all)]
+    Unknown(u8),
+}
+
+serde_primitive!(UIScaling => u8);
+display_unknown!(UIScaling);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown() {
+        assert_eq!(Profession::from(255), Profession::Unknown(255));
+        assert_eq!(u8::from(Profession::from(255)), 255);
+        assert_eq!(Profession::from(7), Profession::Mesmer);
+        assert_eq!(u8::from(Profession::Mesmer), 7);
+        assert_eq!(Race::from(255), Race::Unknown(255));
+        assert_eq!(u8::from(Race::Unknown(255)), 255);
+        assert_eq!(UIScaling::from(255), UIScaling::Unknown(255));
+        assert_eq!(u8::from(UIScaling::Unknown(255)), 255);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Profession::Mesmer.to_string(), "Mesmer");
+        assert_eq!(Profession::Unknown(255).to_string(), "Unknown(255)");
+        assert_eq!(Profession::Unknown(255).as_ref(), "Unknown");
+        assert_eq!(<&str>::from(Profession::Unknown(255)), "Unknown");
+        assert_eq!(Race::Unknown(10).to_string(), "Unknown(10)");
+        assert_eq!(UIScaling::Unknown(4).to_string(), "Unknown(4)");
+    }
 }
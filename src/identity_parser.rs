@@ -0,0 +1,475 @@
+use crate::{until_nul, CharacterName, Identity, IdentityError};
+use std::str::FromStr;
+
+impl Identity {
+    /// Parses the identity JSON from wide chars until the first `0`.
+    ///
+    /// Specialized for the identity schema written by the game and does not allocate.
+    /// Unknown keys are ignored.
+    pub fn from_wide(wide: &[u16]) -> Result<Self, IdentityError> {
+        Parser::new(until_nul(wide)).parse_identity()
+    }
+}
+
+/// Parser for the identity JSON.
+struct Parser<'a> {
+    input: &'a [u16],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn new(input: &'a [u16]) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn parse_identity(&mut self) -> Result<Identity, IdentityError> {
+        let mut name = None;
+        let mut profession = None;
+        let mut spec = None;
+        let mut race = None;
+        let mut map_id = None;
+        let mut world_id = None;
+        let mut team_color_id = None;
+        let mut commander = None;
+        let mut fov = None;
+        let mut ui_scale = None;
+
+        self.expect(b'{')?;
+        if !self.consume(b'}') {
+            loop {
+                let mut buffer = [0; 16];
+                let key = self.parse_key(&mut buffer)?;
+                self.expect(b':')?;
+                self.skip_whitespace();
+                match key {
+                    "name" => {
+                        let mut value = CharacterName::new();
+                        let mut overflow = false;
+                        self.parse_string(|char| overflow |= value.push(char).is_err())?;
+                        if overflow {
+                            return Err(IdentityError::InvalidField("name"));
+                        }
+                        name = Some(value);
+                    }
+                    "profession" => {
+                        let value: u8 = self.parse_number("profession")?;
+                        profession = Some(value.into());
+                    }
+                    "spec" => spec = Some(self.parse_number("spec")?),
+                    "race" => {
+                        let value: u8 = self.parse_number("race")?;
+                        race = Some(value.into());
+                    }
+                    "map_id" => map_id = Some(self.parse_number("map_id")?),
+                    "world_id" => world_id = Some(self.parse_number("world_id")?),
+                    "team_color_id" => team_color_id = Some(self.parse_number("team_color_id")?),
+                    "commander" => commander = Some(self.parse_bool("commander")?),
+                    "fov" => fov = Some(self.parse_number("fov")?),
+                    "uisz" => {
+                        let value: u8 = self.parse_number("uisz")?;
+                        ui_scale = Some(value.into());
+                    }
+                    _ => self.skip_value()?,
+                }
+
+                self.skip_whitespace();
+                if self.consume(b'}') {
+                    break;
+                }
+                self.expect(b',')?;
+            }
+        }
+
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.syntax_error());
+        }
+
+        Ok(Identity {
+            name: name.ok_or(IdentityError::MissingField("name"))?,
+            profession: profession.ok_or(IdentityError::MissingField("profession"))?,
+            spec: spec.ok_or(IdentityError::MissingField("spec"))?,
+            race: race.ok_or(IdentityError::MissingField("race"))?,
+            map_id: map_id.ok_or(IdentityError::MissingField("map_id"))?,
+            world_id: world_id.ok_or(IdentityError::MissingField("world_id"))?,
+            team_color_id: team_color_id.ok_or(IdentityError::MissingField("team_color_id"))?,
+            commander: commander.ok_or(IdentityError::MissingField("commander"))?,
+            fov: fov.ok_or(IdentityError::MissingField("fov"))?,
+            ui_scale: ui_scale.ok_or(IdentityError::MissingField("uisz"))?,
+        })
+    }
+
+    #[inline]
+    fn syntax_error(&self) -> IdentityError {
+        IdentityError::Syntax(self.pos)
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u16> {
+        self.input.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        let unit = self.peek()?;
+        self.pos += 1;
+        Some(unit)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(0x20 | 0x09 | 0x0a | 0x0d) = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes the given ASCII char if it is next after whitespace.
+    fn consume(&mut self, char: u8) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(char.into()) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Expects the given ASCII char after whitespace.
+    fn expect(&mut self, char: u8) -> Result<(), IdentityError> {
+        if self.consume(char) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    /// Parses an object key into the buffer.
+    ///
+    /// Keys not fitting into the buffer are returned as empty string.
+    fn parse_key<'b>(&mut self, buffer: &'b mut [u8]) -> Result<&'b str, IdentityError> {
+        self.skip_whitespace();
+        let mut len = 0;
+        let mut overflow = false;
+        self.parse_string(|char| {
+            if !overflow && len + char.len_utf8() <= buffer.len() {
+                len += char.encode_utf8(&mut buffer[len..]).len();
+            } else {
+                overflow = true;
+            }
+        })?;
+        if overflow {
+            Ok("")
+        } else {
+            Ok(std::str::from_utf8(&buffer[..len]).unwrap_or_default())
+        }
+    }
+
+    /// Parses a string, passing each [`char`] to the sink.
+    fn parse_string(&mut self, mut sink: impl FnMut(char)) -> Result<(), IdentityError> {
+        if self.next() != Some(b'"'.into()) {
+            return Err(IdentityError::Syntax(self.pos.saturating_sub(1)));
+        }
+        loop {
+            let start = self.pos;
+            let unit = self.next().ok_or_else(|| self.syntax_error())?;
+            let unit = match unit {
+                0x22 => return Ok(()),
+                0x5c => match self.next().ok_or_else(|| self.syntax_error())? {
+                    0x22 => 0x22,
+                    0x5c => 0x5c,
+                    0x2f => 0x2f,
+                    0x62 => 0x08,
+                    0x66 => 0x0c,
+                    0x6e => 0x0a,
+                    0x72 => 0x0d,
+                    0x74 => 0x09,
+                    0x75 => self.parse_hex()?,
+                    _ => return Err(IdentityError::Syntax(start)),
+                },
+                unit => unit,
+            };
+
+            let char = match unit {
+                0xd800..=0xdbff => {
+                    let low = match self.peek() {
+                        Some(0x5c) => {
+                            self.pos += 1;
+                            if self.next() != Some(b'u'.into()) {
+                                return Err(IdentityError::Syntax(start));
+                            }
+                            self.parse_hex()?
+                        }
+                        Some(unit) => {
+                            self.pos += 1;
+                            unit
+                        }
+                        None => return Err(self.syntax_error()),
+                    };
+                    if !(0xdc00..=0xdfff).contains(&low) {
+                        return Err(IdentityError::Syntax(start));
+                    }
+                    let code =
+                        0x10000 + ((u32::from(unit) - 0xd800) << 10) + (u32::from(low) - 0xdc00);
+                    char::from_u32(code)
+                }
+                0xdc00..=0xdfff => None,
+                _ => char::from_u32(unit.into()),
+            };
+            sink(char.ok_or(IdentityError::Syntax(start))?);
+        }
+    }
+
+    /// Parses 4 hex digits of an unicode escape.
+    fn parse_hex(&mut self) -> Result<u16, IdentityError> {
+        let mut value = 0;
+        for _ in 0..4 {
+            let digit = self
+                .next()
+                .and_then(|unit| char::from_u32(unit.into()))
+                .and_then(|char| char.to_digit(16))
+                .ok_or_else(|| IdentityError::Syntax(self.pos.saturating_sub(1)))?;
+            value = (value << 4) | digit as u16;
+        }
+        Ok(value)
+    }
+
+    /// Parses a number for the given field.
+    fn parse_number<T>(&mut self, field: &'static str) -> Result<T, IdentityError>
+    where
+        T: FromStr,
+    {
+        let mut buffer = [0; 32];
+        let len = self.scan_number(&mut buffer)?;
+        std::str::from_utf8(&buffer[..len])
+            .ok()
+            .and_then(|string| string.parse().ok())
+            .ok_or(IdentityError::InvalidField(field))
+    }
+
+    /// Scans a number into the buffer and returns its length.
+    fn scan_number(&mut self, buffer: &mut [u8]) -> Result<usize, IdentityError> {
+        let start = self.pos;
+        let mut len = 0;
+        while let Some(unit @ (0x30..=0x39 | 0x2b | 0x2d | 0x2e | 0x45 | 0x65)) = self.peek() {
+            *buffer.get_mut(len).ok_or(IdentityError::Syntax(start))? = unit as u8;
+            len += 1;
+            self.pos += 1;
+        }
+        if len == 0 {
+            Err(IdentityError::Syntax(start))
+        } else {
+            Ok(len)
+        }
+    }
+
+    /// Parses a boolean for the given field.
+    fn parse_bool(&mut self, field: &'static str) -> Result<bool, IdentityError> {
+        if self.consume_literal("true") {
+            Ok(true)
+        } else if self.consume_literal("false") {
+            Ok(false)
+        } else {
+            Err(IdentityError::InvalidField(field))
+        }
+    }
+
+    /// Consumes the given ASCII literal if it is next.
+    fn consume_literal(&mut self, literal: &str) -> bool {
+        let end = self.pos + literal.len();
+        let matches = self.input.get(self.pos..end).is_some_and(|units| {
+            units
+                .iter()
+                .zip(literal.bytes())
+                .all(|(unit, byte)| *unit == u16::from(byte))
+        });
+        if matches {
+            self.pos = end;
+        }
+        matches
+    }
+
+    /// Skips any JSON value.
+    fn skip_value(&mut self) -> Result<(), IdentityError> {
+        self.skip_whitespace();
+        match self.peek().ok_or_else(|| self.syntax_error())? {
+            0x22 => self.parse_string(|_| {}),
+            0x7b => self.skip_container(b'}', true),
+            0x5b => self.skip_container(b']', false),
+            _ => {
+                if !(self.consume_literal("true")
+                    || self.consume_literal("false")
+                    || self.consume_literal("null"))
+                {
+                    self.scan_number(&mut [0; 64])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Skips an object or array.
+    fn skip_container(&mut self, end: u8, object: bool) -> Result<(), IdentityError> {
+        self.pos += 1;
+        if self.consume(end) {
+            return Ok(());
+        }
+        loop {
+            if object {
+                self.skip_whitespace();
+                self.parse_string(|_| {})?;
+                self.expect(b':')?;
+            }
+            self.skip_value()?;
+            if self.consume(end) {
+                return Ok(());
+            }
+            self.expect(b',')?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Profession, Race, UIScaling};
+
+    const IDENTITY: &str = r#"{"name":"Test Character","profession":6,"spec":48,"race":4,"map_id":1206,"world_id":2003,"team_color_id":0,"commander":true,"fov":0.873,"uisz":1}"#;
+
+    fn parse(json: &str) -> Result<Identity, IdentityError> {
+        let wide: Vec<u16> = json.encode_utf16().collect();
+        Identity::from_wide(&wide)
+    }
+
+    fn with_name(name: &str) -> String {
+        IDENTITY.replace(r#""Test Character""#, name)
+    }
+
+    #[test]
+    fn identity() {
+        let identity = parse(IDENTITY).unwrap();
+        assert_eq!(identity.name, "Test Character");
+        assert_eq!(identity.profession, Profession::Elementalist);
+        assert_eq!(identity.spec, 48);
+        assert_eq!(identity.race, Race::Sylvari);
+        assert_eq!(identity.map_id, 1206);
+        assert_eq!(identity.world_id, 2003);
+        assert_eq!(identity.team_color_id, 0);
+        assert!(identity.commander);
+        assert_eq!(identity.fov, 0.873);
+        assert_eq!(identity.ui_scale, UIScaling::Normal);
+    }
+
+    #[test]
+    fn stops_at_nul() {
+        let mut wide: Vec<u16> = IDENTITY.encode_utf16().collect();
+        wide.extend([0, b'x'.into(), b'y'.into()]);
+        assert_eq!(Identity::from_wide(&wide).unwrap().name, "Test Character");
+    }
+
+    #[test]
+    fn whitespace_and_unknown_keys() {
+        let json = IDENTITY.replace(
+            r#""spec":48,"#,
+            r#" "spec" : 48 , "extra": {"nested": [1, -2.5e3, {"deep": "}]"}, true, null], "empty": {}}, "list": [], "a_very_long_unknown_key": "\"x\"","#,
+        );
+        let identity = parse(&json).unwrap();
+        assert_eq!(identity.spec, 48);
+        assert_eq!(identity.map_id, 1206);
+    }
+
+    #[test]
+    fn escapes() {
+        let identity = parse(&with_name(r#""A\"\\\/\n\u00e9""#)).unwrap();
+        assert_eq!(identity.name, "A\"\\/\né");
+    }
+
+    #[test]
+    fn surrogates() {
+        let escaped = parse(&with_name(r#""\ud83d\ude00""#)).unwrap();
+        assert_eq!(escaped.name, "\u{1f600}");
+
+        let raw = parse(&with_name("\"\u{1f600}\"")).unwrap();
+        assert_eq!(raw.name, "\u{1f600}");
+
+        let mixed: Vec<u16> = with_name(r#""\ud83dX""#)
+            .encode_utf16()
+            .map(|unit| {
+                if unit == u16::from(b'X') {
+                    0xde00
+                } else {
+                    unit
+                }
+            })
+            .collect();
+        assert_eq!(Identity::from_wide(&mixed).unwrap().name, "\u{1f600}");
+
+        let lone_low = parse(&with_name(r#""\udc00""#));
+        assert!(matches!(lone_low, Err(IdentityError::Syntax(_))));
+
+        let lone_high = parse(&with_name(r#""\ud83dx""#));
+        assert!(matches!(lone_high, Err(IdentityError::Syntax(_))));
+    }
+
+    #[test]
+    fn name_capacity() {
+        let max = "a".repeat(CharacterName::CAPACITY);
+        let identity = parse(&with_name(&format!("\"{max}\""))).unwrap();
+        assert_eq!(identity.name, max.as_str());
+
+        let overflow = "a".repeat(CharacterName::CAPACITY + 1);
+        assert_eq!(
+            parse(&with_name(&format!("\"{overflow}\""))).unwrap_err(),
+            IdentityError::InvalidField("name")
+        );
+    }
+
+    #[test]
+    fn missing_field() {
+        let json = IDENTITY.replace(r#","fov":0.873"#, "");
+        assert_eq!(
+            parse(&json).unwrap_err(),
+            IdentityError::MissingField("fov")
+        );
+    }
+
+    #[test]
+    fn invalid_field() {
+        let json = IDENTITY.replace(r#""commander":true"#, r#""commander":1"#);
+        assert_eq!(
+            parse(&json).unwrap_err(),
+            IdentityError::InvalidField("commander")
+        );
+
+        let json = IDENTITY.replace(r#""profession":6"#, r#""profession":256"#);
+        assert_eq!(
+            parse(&json).unwrap_err(),
+            IdentityError::InvalidField("profession")
+        );
+    }
+
+    #[test]
+    fn syntax_errors() {
+        let trailing = format!("{IDENTITY} x");
+        assert_eq!(
+            parse(&trailing).unwrap_err(),
+            IdentityError::Syntax(IDENTITY.len() + 1)
+        );
+
+        assert_eq!(parse("").unwrap_err(), IdentityError::Syntax(0));
+        assert_eq!(parse("[]").unwrap_err(), IdentityError::Syntax(0));
+        assert_eq!(
+            parse(r#"{"name" "x"}"#).unwrap_err(),
+            IdentityError::Syntax(8)
+        );
+        assert_eq!(
+            parse(&with_name(r#""\q""#)).unwrap_err(),
+            IdentityError::Syntax(9)
+        );
+
+        let unterminated = &IDENTITY[..IDENTITY.len() - 1];
+        assert_eq!(
+            parse(unterminated).unwrap_err(),
+            IdentityError::Syntax(unterminated.len())
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Conversions to and from math library types.
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use crate::{Camera, Continent, MapInches, MapMeters, Position, Ray};
+    use glam::{Mat4, Vec2, Vec3};
+
+    impl Position {
+        /// Returns the position as [`Vec3`].
+        #[inline]
+        pub fn position_vec3(&self) -> Vec3 {
+            self.position.into()
+        }
+
+        /// Returns the front vector as [`Vec3`].
+        #[inline]
+        pub fn front_vec3(&self) -> Vec3 {
+            self.front.into()
+        }
+
+        /// Returns the top vector as [`Vec3`].
+        #[inline]
+        pub fn top_vec3(&self) -> Vec3 {
+            self.top.into()
+        }
+    }
+
+    impl Camera {
+        /// Returns the view matrix as [`Mat4`].
+        #[inline]
+        pub fn view_mat4(&self) -> Mat4 {
+            Mat4::from_cols_array_2d(&self.view_matrix())
+        }
+
+        /// Returns the projection matrix for the given viewport size as [`Mat4`].
+        #[inline]
+        pub fn projection_mat4(&self, viewport: [f32; 2]) -> Mat4 {
+            Mat4::from_cols_array_2d(&self.projection_matrix(viewport))
+        }
+
+        /// Returns the combined view projection matrix for the given viewport size as [`Mat4`].
+        #[inline]
+        pub fn view_projection_mat4(&self, viewport: [f32; 2]) -> Mat4 {
+            Mat4::from_cols_array_2d(&self.view_projection_matrix(viewport))
+        }
+    }
+
+    impl Ray {
+        /// Returns the origin as [`Vec3`].
+        #[inline]
+        pub fn origin_vec3(&self) -> Vec3 {
+            self.origin.into()
+        }
+
+        /// Returns the direction as [`Vec3`].
+        #[inline]
+        pub fn direction_vec3(&self) -> Vec3 {
+            self.direction.into()
+        }
+    }
+
+    macro_rules! impl_glam {
+        ( $( $ty:ty => $vec:ty ),* ) => {
+            $(
+                impl From<$ty> for $vec {
+                    #[inline]
+                    fn from(value: $ty) -> Self {
+                        value.0.into()
+                    }
+                }
+
+                impl From<$vec> for $ty {
+                    #[inline]
+                    fn from(value: $vec) -> Self {
+                        Self(value.into())
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_glam!(MapMeters => Vec3, MapInches => Vec3, Continent => Vec2);
+}
+
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use crate::{Camera, Continent, MapInches, MapMeters, Position, Ray};
+    use mint::{ColumnMatrix4, Point2, Point3, Vector3};
+
+    impl Position {
+        /// Returns the position as [`Point3`].
+        #[inline]
+        pub fn position_mint(&self) -> Point3<f32> {
+            self.position.into()
+        }
+
+        /// Returns the front vector as [`Vector3`].
+        #[inline]
+        pub fn front_mint(&self) -> Vector3<f32> {
+            self.front.into()
+        }
+
+        /// Returns the top vector as [`Vector3`].
+        #[inline]
+        pub fn top_mint(&self) -> Vector3<f32> {
+            self.top.into()
+        }
+    }
+
+    impl Camera {
+        /// Returns the view matrix as [`ColumnMatrix4`].
+        #[inline]
+        pub fn view_matrix_mint(&self) -> ColumnMatrix4<f32> {
+            self.view_matrix().into()
+        }
+
+        /// Returns the projection matrix for the given viewport size as [`ColumnMatrix4`].
+        #[inline]
+        pub fn projection_matrix_mint(&self, viewport: [f32; 2]) -> ColumnMatrix4<f32> {
+            self.projection_matrix(viewport).into()
+        }
+
+        /// Returns the combined view projection matrix for the given viewport size as [`ColumnMatrix4`].
+        #[inline]
+        pub fn view_projection_matrix_mint(&self, viewport: [f32; 2]) -> ColumnMatrix4<f32> {
+            self.view_projection_matrix(viewport).into()
+        }
+    }
+
+    impl Ray {
+        /// Returns the origin as [`Point3`].
+        #[inline]
+        pub fn origin_mint(&self) -> Point3<f32> {
+            self.origin.into()
+        }
+
+        /// Returns the direction as [`Vector3`].
+        #[inline]
+        pub fn direction_mint(&self) -> Vector3<f32> {
+            self.direction.into()
+        }
+    }
+
+    macro_rules! impl_mint {
+        ( $( $ty:ty => $point:ty ),* ) => {
+            $(
+                impl From<$ty> for $point {
+                    #[inline]
+                    fn from(value: $ty) -> Self {
+                        value.0.into()
+                    }
+                }
+
+                impl From<$point> for $ty {
+                    #[inline]
+                    fn from(value: $point) -> Self {
+                        Self(value.into())
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_mint!(MapMeters => Point3<f32>, MapInches => Point3<f32>, Continent => Point2<f32>);
+}
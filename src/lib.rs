@@ -8,73 +8,130 @@
 //! let player_pos = mumble.read_avatar();
 //! ```
 //!
+//! Code generic over live shared memory, owned snapshots and custom sources can be written using the [`LinkSource`] trait.
+//!
+//! The MumbleLink keeps its last contents after the game exits. Stale data can be detected using the [`Liveness`] tracker.
+//! Loading screens and character select can be followed using the [`GameStateMachine`].
+//!
+//! On Windows the MumbleLink is opened as named file mapping.
+//! On Unix platforms it is opened as POSIX shared memory object, for example `/dev/shm/MumbleLink` on Linux.
+//!
+//! Test setups and simulators can publish data the same way the game does using [`MumbleWriter`].
+//!
+//! Sessions can be recorded and read back frame by frame using the [`recording`] module.
+//! Avatar routes can be recorded to TacO and Blish HUD `.trl` trails using the [`trail`] module.
+//!
 //! [Serde](https://serde.rs) support can be enabled with the `"serde"` feature.
 //!
+//! Conversions to and from [`glam`](https://docs.rs/glam) and [`mint`](https://docs.rs/mint) types are available with the `"glam"` and `"mint"` features.
+//!
+//! An asynchronous `Stream` of updates is available as `LinkStream` when enabling the `"tokio"` feature.
+//!
 //! Parsing of the player identity JSON is supported when enabling the `"json"` feature:
 //! ```ignore
 //! let identity = mumble.parse_identity();
 //! ```
+//!
+//! Alternatively the player identity can be parsed without allocating and without any features:
+//! ```no_run
+//! # let mumble = gw2_mumble::MumbleLink::new().unwrap();
+//! let identity = mumble.decode_identity();
+//! ```
 
+mod camera;
+mod compass;
 mod context;
+mod coords;
 mod error;
+mod game_state;
 mod identity;
+mod identity_parser;
+#[cfg(any(feature = "glam", feature = "mint"))]
+mod interop;
 mod link_ptr;
 mod linked_mem;
+mod liveness;
+#[cfg(any(windows, unix))]
+mod mapping;
+mod math;
+mod position;
+#[cfg(feature = "serde")]
+mod serde_util;
+mod snapshot;
+mod sockaddr;
+mod source;
+mod specialization;
+#[cfg(feature = "tokio")]
+mod stream;
 mod util;
+mod watcher;
+mod wide;
+#[cfg(any(windows, unix))]
+mod writer;
 
 pub mod map_id;
 pub mod map_type;
+pub mod recording;
+pub mod trail;
+
+pub use self::map_type::MapType;
+pub use self::{
+    camera::*, compass::*, context::*, coords::*, error::*, game_state::*, identity::*,
+    link_ptr::*, linked_mem::*, liveness::*, snapshot::*, sockaddr::*, source::*,
+    specialization::*, watcher::*, wide::*,
+};
 
-pub use self::{context::*, error::*, identity::*, link_ptr::*, linked_mem::*};
+#[cfg(feature = "tokio")]
+pub use self::stream::*;
 
-use std::{env, ffi::CString, io, mem};
-#[cfg(windows)]
-use windows::{
-    core::{Free, PCSTR},
-    Win32::{
-        Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE},
-        System::Memory::{CreateFileMappingA, MapViewOfFile, FILE_MAP_READ, PAGE_READWRITE},
-    },
-};
+#[cfg(any(windows, unix))]
+pub use self::writer::*;
+
+use std::env;
+#[cfg(unix)]
+use std::path::Path;
 
 /// Access point to the MumbleLink memory shared file.
 #[derive(Debug)]
-#[cfg(windows)]
+#[cfg(any(windows, unix))]
 pub struct MumbleLink {
-    handle: HANDLE,
+    _mapping: mapping::Mapping,
     ptr: MumblePtr,
 }
 
-#[cfg(windows)]
+#[cfg(any(windows, unix))]
 impl MumbleLink {
     /// Creates a new access point to the MumbleLink.
+    ///
+    /// The name is resolved via [`MumbleLink::link_name`].
+    #[inline]
     pub fn new() -> Result<Self, Error> {
-        const SIZE: usize = mem::size_of::<LinkedMem>();
+        Self::with_name(&Self::link_name())
+    }
 
-        let name = Self::link_name();
-        if name == "0" {
-            return Err(Error::Disabled);
-        }
-        let name = CString::new(name)?;
-
-        let handle = unsafe {
-            CreateFileMappingA(
-                INVALID_HANDLE_VALUE,
-                None,
-                PAGE_READWRITE,
-                0,
-                SIZE as u32,
-                PCSTR::from_raw(name.as_ptr().cast()),
-            )?
-        };
+    /// Creates a new access point to the MumbleLink with the given name.
+    ///
+    /// On Unix platforms names containing a `/` are treated as file path, see [`MumbleLink::open_path`].
+    /// Other names are opened as POSIX shared memory object, for example `/dev/shm/MumbleLink` on Linux.
+    #[inline]
+    pub fn with_name(name: &str) -> Result<Self, Error> {
+        mapping::Mapping::open(name, false).map(Self::from_mapping)
+    }
+
+    /// Creates a new access point to a MumbleLink backed by the file at the given path.
+    #[inline]
+    #[cfg(unix)]
+    pub fn open_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        mapping::Mapping::open_path(path, false).map(Self::from_mapping)
+    }
 
-        let ptr = unsafe { MapViewOfFile(handle, FILE_MAP_READ, 0, 0, SIZE) }.Value;
-        if let Some(ptr) = unsafe { MumblePtr::new(ptr.cast()) } {
-            Ok(Self { handle, ptr })
-        } else {
-            let err = io::Error::last_os_error();
-            let _ = unsafe { CloseHandle(handle) };
-            Err(err.into())
+    #[inline]
+    fn from_mapping(mapping: mapping::Mapping) -> Self {
+        let ptr =
+            unsafe { MumblePtr::new(mapping.as_non_null().as_ptr()) }.expect("mapping is null");
+        Self {
+            _mapping: mapping,
+            ptr,
         }
     }
 
@@ -93,21 +150,7 @@ impl MumbleLink {
     }
 }
 
-#[cfg(windows)]
-unsafe impl Send for MumbleLink {}
-
-#[cfg(windows)]
-unsafe impl Sync for MumbleLink {}
-
-#[cfg(windows)]
-impl Drop for MumbleLink {
-    #[inline]
-    fn drop(&mut self) {
-        unsafe { self.handle.free() }
-    }
-}
-
-#[cfg(windows)]
+#[cfg(any(windows, unix))]
 impl std::ops::Deref for MumbleLink {
     type Target = MumblePtr;
 
@@ -116,3 +159,19 @@ impl std::ops::Deref for MumbleLink {
         &self.ptr
     }
 }
+
+#[cfg(test)]
+mod test_util {
+    use crate::LinkedMem;
+
+    /// Returns a [`LinkedMem`] written by the process with the given `ui_tick`.
+    pub fn mem(tick: u32, process_id: u32) -> LinkedMem {
+        let mut mem = LinkedMem {
+            ui_version: 2,
+            ui_tick: tick,
+            ..LinkedMem::default()
+        };
+        mem.context.process_id = process_id;
+        mem
+    }
+}
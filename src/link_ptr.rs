@@ -1,7 +1,12 @@
-use crate::{util::read_until_nul, Context, LinkedMem, Mount, Position, UiState};
-use std::ptr::NonNull;
-#[cfg(windows)]
-use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+use crate::{
+    decode_sockaddr, decode_wide, decode_wide_lossy, util::read_until_nul, Context, DecodeError,
+    Identity, IdentityError, LinkedMem, MapType, Mount, Position, RawContext, Snapshot, UiState,
+};
+use std::{
+    net::SocketAddr,
+    ptr::NonNull,
+    sync::atomic::{fence, Ordering},
+};
 
 /// A pointer to [`LinkedMem`] with utility.
 #[derive(Debug, Clone, Copy)]
@@ -45,6 +50,48 @@ impl MumblePtr {
         unsafe { self.as_ptr().read_volatile() }
     }
 
+    /// Default number of attempts for [`MumblePtr::read_consistent`].
+    pub const CONSISTENT_READ_ATTEMPTS: u32 = 8;
+
+    /// Reads the entire current [`LinkedMem`] contents without mixing data from different frames.
+    ///
+    /// Uses the `ui_tick` as sequence marker and retries the read if it changed during the copy.
+    /// See [`MumblePtr::read_consistent_with`] for details.
+    #[inline]
+    pub fn read_consistent(&self) -> Snapshot {
+        self.read_consistent_with(Self::CONSISTENT_READ_ATTEMPTS)
+    }
+
+    /// Reads the entire current [`LinkedMem`] contents without mixing data from different frames.
+    ///
+    /// Reads the `ui_tick`, copies the contents and checks the `ui_tick` again.
+    /// If the tick changed, the read is retried up to the given number of attempts.
+    /// At least one attempt is always made.
+    /// The returned [`Snapshot`] records whether consistency was confirmed.
+    ///
+    /// Writes within a single frame can not be detected, as the game only advances the `ui_tick` once per frame.
+    pub fn read_consistent_with(&self, max_attempts: u32) -> Snapshot {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let before = self.read_ui_tick();
+            fence(Ordering::Acquire);
+            let mem = self.read();
+            fence(Ordering::Acquire);
+            let after = self.read_ui_tick();
+
+            let consistent = before == after && mem.ui_tick == before;
+            if consistent || attempts >= max_attempts {
+                return Snapshot {
+                    mem,
+                    consistent,
+                    attempts,
+                };
+            }
+            std::hint::spin_loop();
+        }
+    }
+
     /// Reads the current `ui_version`.
     #[inline]
     pub fn read_ui_version(&self) -> u32 {
@@ -69,11 +116,18 @@ impl MumblePtr {
         read_until_nul(member_ptr!(self.name))
     }
 
-    /// Reads the current game name as [`OsString`].
+    /// Reads the current game name as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    pub fn read_name_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_name())
+    }
+
+    /// Reads the current game name as [`String`], replacing invalid UTF-16.
     #[inline]
-    #[cfg(windows)]
-    pub fn read_name_string(&self) -> OsString {
-        OsString::from_wide(&self.read_name())
+    pub fn read_name_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_name())
     }
 
     /// Reads the current camera [`Position`].
@@ -88,23 +142,43 @@ impl MumblePtr {
         read_until_nul(member_ptr!(self.identity))
     }
 
-    /// Reads the current player identity as [`OsString`].
+    /// Reads the current player identity as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    pub fn read_identity_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_identity())
+    }
+
+    /// Reads the current player identity as [`String`], replacing invalid UTF-16.
     #[inline]
-    #[cfg(windows)]
-    pub fn read_identity_string(&self) -> OsString {
-        OsString::from_wide(&self.read_identity())
+    pub fn read_identity_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_identity())
+    }
+
+    /// Parses the current player identity JSON contents without allocating.
+    ///
+    /// See [`Identity::from_wide`].
+    #[inline]
+    pub fn decode_identity(&self) -> Result<Identity, IdentityError> {
+        Identity::from_wide(&read_member!(self.identity))
     }
 
     /// Parses the current player identity JSON contents.
     #[cfg(feature = "json")]
-    pub fn parse_identity(&self) -> serde_json::Result<crate::Identity> {
-        let string = self.read_identity_string();
-        serde_json::from_str(&string.to_string_lossy())
+    pub fn parse_identity(&self) -> serde_json::Result<Identity> {
+        serde_json::from_str(&self.read_identity_string_lossy())
     }
 
     /// Reads the current [`Context`].
     #[inline]
     pub fn read_context(&self) -> Context {
+        self.read_raw_context().into()
+    }
+
+    /// Reads the current [`RawContext`].
+    #[inline]
+    pub fn read_raw_context(&self) -> RawContext {
         read_member!(self.context)
     }
 
@@ -114,6 +188,12 @@ impl MumblePtr {
         read_member!(self.context.server_address)
     }
 
+    /// Reads the current server address as [`SocketAddr`].
+    #[inline]
+    pub fn read_server_socket_addr(&self) -> Option<SocketAddr> {
+        decode_sockaddr(&self.read_server_address())
+    }
+
     /// Reads the current map id.
     #[inline]
     pub fn read_map_id(&self) -> u32 {
@@ -126,6 +206,12 @@ impl MumblePtr {
         read_member!(self.context.map_type)
     }
 
+    /// Reads the current map type as [`MapType`].
+    #[inline]
+    pub fn read_map_type_typed(&self) -> MapType {
+        self.read_map_type().into()
+    }
+
     /// Reads the current shard id.
     #[inline]
     pub fn read_shard_id(&self) -> u32 {
@@ -147,7 +233,7 @@ impl MumblePtr {
     /// Reads the current UI state.
     #[inline]
     pub fn read_ui_state(&self) -> UiState {
-        read_member!(self.context.ui_state)
+        UiState::from_bits_retain(read_member!(self.context.ui_state))
     }
 
     /// Reads the current compass width in pixels.
@@ -237,7 +323,7 @@ impl MumblePtr {
     /// Reads the currently used mount.
     #[inline]
     pub fn read_mount_index(&self) -> Mount {
-        read_member!(self.context.mount_index)
+        Mount::from(read_member!(self.context.mount_index))
     }
 
     /// Reads the game description.
@@ -246,11 +332,18 @@ impl MumblePtr {
         read_until_nul(member_ptr!(self.description))
     }
 
-    /// Reads the game description as [`OsString`].
+    /// Reads the game description as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    pub fn read_description_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_description())
+    }
+
+    /// Reads the game description as [`String`], replacing invalid UTF-16.
     #[inline]
-    #[cfg(windows)]
-    pub fn read_description_string(&self) -> OsString {
-        OsString::from_wide(&self.read_description())
+    pub fn read_description_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_description())
     }
 }
 
@@ -1,9 +1,11 @@
-use crate::{util::until_nul, Context};
-#[cfg(windows)]
-use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+use crate::{
+    decode_chars, decode_wide, decode_wide_lossy, Context, DecodeError, Identity, IdentityError,
+    RawContext, WideChars,
+};
 
 /// MumbleLink shared memory.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct LinkedMem {
     /// UI version.
@@ -14,12 +16,14 @@ pub struct LinkedMem {
 
     /// Position of the player in map coordinate system.
     ///
+    /// Positions are in meters, see [`MapMeters`](crate::MapMeters) for conversions.
     /// See [API:1/event_details#Coordinate_recalculation](https://wiki.guildwars2.com/wiki/API:1/event_details#Coordinate_recalculation).
     ///
     /// Updated every frame. Should be able to read 50 times a second.
     pub avatar: Position,
 
     /// Game name.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::wide"))]
     pub name: [u16; 256],
 
     /// Position of the camera.
@@ -30,54 +34,126 @@ pub struct LinkedMem {
     /// Identity information as JSON.
     ///
     /// Should only change a few times per second.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::wide"))]
     pub identity: [u16; 256],
 
     /// Length of the following context.
     ///
-    /// Hardcoded to `48` for Guild Wars 2 despite [`Context`] being larger.
+    /// Hardcoded to `48` for Guild Wars 2 despite [`RawContext`] being larger.
     pub context_len: u32,
 
-    /// See [`Context`].
+    /// See [`RawContext`] and [`Context`].
     ///
     /// Should only change a few times per second.
-    pub context: Context,
+    pub context: RawContext,
 
     /// Game description.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_util::wide"))]
     pub description: [u16; 2048],
 }
 
 impl LinkedMem {
-    /// Returns the name as [`OsString`].
+    /// Returns the game name as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    pub fn name_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.name)
+    }
+
+    /// Returns the game name as [`String`], replacing invalid UTF-16.
+    #[inline]
+    pub fn name_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.name)
+    }
+
+    /// Returns an iterator over the [`char`]s of the game name.
+    #[inline]
+    pub fn name_chars(&self) -> WideChars<'_> {
+        decode_chars(&self.name)
+    }
+
+    /// Returns the identity JSON as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    pub fn identity_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.identity)
+    }
+
+    /// Returns the identity JSON as [`String`], replacing invalid UTF-16.
+    #[inline]
+    pub fn identity_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.identity)
+    }
+
+    /// Returns an iterator over the [`char`]s of the identity JSON.
     #[inline]
-    #[cfg(windows)]
-    pub fn name_string(&self) -> OsString {
-        OsString::from_wide(until_nul(&self.name))
+    pub fn identity_chars(&self) -> WideChars<'_> {
+        decode_chars(&self.identity)
     }
 
-    /// Returns the name as [`OsString`].
+    /// Returns the game description as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
     #[inline]
-    #[cfg(windows)]
-    pub fn identity_string(&self) -> OsString {
-        OsString::from_wide(until_nul(&self.identity))
+    pub fn description_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.description)
     }
 
-    /// Returns the name as [`OsString`].
+    /// Returns the game description as [`String`], replacing invalid UTF-16.
     #[inline]
-    #[cfg(windows)]
-    pub fn description_string(&self) -> OsString {
-        OsString::from_wide(until_nul(&self.description))
+    pub fn description_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.description)
+    }
+
+    /// Returns an iterator over the [`char`]s of the game description.
+    #[inline]
+    pub fn description_chars(&self) -> WideChars<'_> {
+        decode_chars(&self.description)
+    }
+
+    /// Returns the typed [`Context`].
+    #[inline]
+    pub fn typed_context(&self) -> Context {
+        Context::from(&self.context)
+    }
+
+    /// Parses the current identity JSON contents without allocating.
+    ///
+    /// See [`Identity::from_wide`].
+    #[inline]
+    pub fn decode_identity(&self) -> Result<Identity, IdentityError> {
+        Identity::from_wide(&self.identity)
     }
 
     /// Parses the current identity JSON contents.
     #[cfg(feature = "json")]
-    pub fn parse_identity(&self) -> serde_json::Result<crate::Identity> {
-        let string = self.identity_string();
-        serde_json::from_str(&string.to_string_lossy())
+    pub fn parse_identity(&self) -> serde_json::Result<Identity> {
+        serde_json::from_str(&self.identity_string_lossy())
+    }
+}
+
+impl Default for LinkedMem {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            ui_version: 0,
+            ui_tick: 0,
+            avatar: Position::default(),
+            name: [0; 256],
+            camera: Position::default(),
+            identity: [0; 256],
+            context_len: 0,
+            context: RawContext::default(),
+            description: [0; 2048],
+        }
     }
 }
 
 /// Position structure.
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Position {
     /// Position in space.
@@ -89,3 +165,6 @@ pub struct Position {
     /// Unit vector pointing out of the top of the head, aka the "Up"-vector.
     pub top: [f32; 3],
 }
+
+// layout has to match the memory shared by the game
+const _: () = assert!(std::mem::size_of::<LinkedMem>() == 5292);
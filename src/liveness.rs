@@ -0,0 +1,332 @@
+use crate::{LinkSource, LinkedMem};
+use std::time::{Duration, Instant};
+
+/// Liveness state of the game writing to the MumbleLink.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::AsRefStr)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LivenessState {
+    /// The MumbleLink has not been written to yet.
+    #[default]
+    #[strum(serialize = "Not started")]
+    NotStarted,
+
+    /// The `ui_tick` is advancing.
+    Running,
+
+    /// The `ui_tick` stalled, for example on a loading screen, but the game has not exited.
+    Paused,
+
+    /// The game has exited and the MumbleLink contents are stale.
+    Exited,
+}
+
+impl LivenessState {
+    /// Returns whether the MumbleLink contents are live.
+    #[inline]
+    pub fn is_live(&self) -> bool {
+        matches!(self, Self::Running | Self::Paused)
+    }
+}
+
+/// Tracks whether the MumbleLink contents are written by a running game.
+///
+/// Based on `ui_tick` advancement and a check whether the [`Context::process_id`](crate::Context::process_id) still exists.
+/// A new process id resets the tracker.
+#[derive(Debug, Clone)]
+pub struct Liveness {
+    pause_threshold: Duration,
+    exit_timeout: Duration,
+    process_check: bool,
+    state: LivenessState,
+    process_id: u32,
+    last_tick: Option<u32>,
+    last_advance: Instant,
+    advanced: bool,
+}
+
+impl Liveness {
+    /// Default time without `ui_tick` advancing before the game is considered [`LivenessState::Paused`].
+    pub const DEFAULT_PAUSE_THRESHOLD: Duration = Duration::from_millis(500);
+
+    /// Default time without `ui_tick` advancing before the game is considered [`LivenessState::Exited`].
+    ///
+    /// Only applies if the process can not be checked.
+    pub const DEFAULT_EXIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Creates a new liveness tracker with the default thresholds.
+    ///
+    /// The process check is enabled by default on Windows only.
+    /// On other platforms the game usually runs under Wine, reporting Windows process ids.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_thresholds(Self::DEFAULT_PAUSE_THRESHOLD, Self::DEFAULT_EXIT_TIMEOUT)
+    }
+
+    /// Creates a new liveness tracker with the given pause threshold and exit timeout.
+    #[inline]
+    pub fn with_thresholds(pause_threshold: Duration, exit_timeout: Duration) -> Self {
+        Self {
+            pause_threshold,
+            exit_timeout,
+            process_check: cfg!(windows),
+            state: LivenessState::NotStarted,
+            process_id: 0,
+            last_tick: None,
+            last_advance: Instant::now(),
+            advanced: false,
+        }
+    }
+
+    /// Returns the tracker with the process check enabled or disabled.
+    #[inline]
+    pub fn with_process_check(self, process_check: bool) -> Self {
+        Self {
+            process_check,
+            ..self
+        }
+    }
+
+    /// Returns the current [`LivenessState`].
+    #[inline]
+    pub fn state(&self) -> LivenessState {
+        self.state
+    }
+
+    /// Returns the currently tracked process id.
+    #[inline]
+    pub fn process_id(&self) -> u32 {
+        self.process_id
+    }
+
+    /// Returns whether the `ui_tick` advanced since the current process id was first seen.
+    #[inline]
+    pub fn has_advanced(&self) -> bool {
+        self.advanced
+    }
+
+    /// Returns the time since the `ui_tick` last advanced.
+    #[inline]
+    pub fn stalled_for(&self) -> Duration {
+        self.last_advance.elapsed()
+    }
+
+    /// Resets the tracker to [`LivenessState::NotStarted`].
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = LivenessState::NotStarted;
+        self.process_id = 0;
+        self.last_tick = None;
+        self.last_advance = Instant::now();
+        self.advanced = false;
+    }
+
+    /// Reads from the source and returns the updated [`LivenessState`].
+    #[inline]
+    pub fn poll(&mut self, source: &impl LinkSource) -> LivenessState {
+        self.update(&source.read())
+    }
+
+    /// Updates with the given [`LinkedMem`] and returns the updated [`LivenessState`].
+    #[inline]
+    pub fn update(&mut self, mem: &LinkedMem) -> LivenessState {
+        self.update_at(mem, Instant::now())
+    }
+
+    /// Updates with the given [`LinkedMem`] read at the given time and returns the updated [`LivenessState`].
+    ///
+    /// A newly seen process id is only considered [`LivenessState::Running`] once its `ui_tick` advances.
+    /// Until then stale contents left behind by an exited game are reported as [`LivenessState::Paused`] or [`LivenessState::Exited`].
+    pub fn update_at(&mut self, mem: &LinkedMem, now: Instant) -> LivenessState {
+        let process_id = mem.context.process_id;
+        if mem.ui_version == 0 && mem.ui_tick == 0 && process_id == 0 {
+            self.reset();
+            return self.state;
+        }
+
+        if self.last_tick.is_none() || process_id != self.process_id {
+            self.process_id = process_id;
+            self.last_tick = Some(mem.ui_tick);
+            self.last_advance = now;
+            self.advanced = false;
+        } else if self.last_tick != Some(mem.ui_tick) {
+            self.last_tick = Some(mem.ui_tick);
+            self.last_advance = now;
+            self.advanced = true;
+        }
+
+        let stalled = now.saturating_duration_since(self.last_advance);
+        self.state = if self.advanced && stalled < self.pause_threshold {
+            LivenessState::Running
+        } else {
+            let alive = (self.process_check && process_id != 0)
+                .then(|| is_process_alive(process_id))
+                .flatten();
+            match alive {
+                Some(true) => LivenessState::Paused,
+                Some(false) => LivenessState::Exited,
+                None if stalled < self.exit_timeout => LivenessState::Paused,
+                None => LivenessState::Exited,
+            }
+        };
+        self.state
+    }
+}
+
+impl Default for Liveness {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks whether a process with the given id is running.
+///
+/// Returns [`None`] if the check is unsupported or fails for other reasons, for example missing permissions.
+#[cfg(windows)]
+pub fn is_process_alive(process_id: u32) -> Option<bool> {
+    use windows::{
+        core::HRESULT,
+        Win32::{
+            Foundation::{CloseHandle, ERROR_INVALID_PARAMETER, STILL_ACTIVE},
+            System::Threading::{
+                GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+            },
+        },
+    };
+
+    let handle = match unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) }
+    {
+        Ok(handle) => handle,
+        Err(err) if err.code() == HRESULT::from_win32(ERROR_INVALID_PARAMETER.0) => {
+            return Some(false)
+        }
+        Err(_) => return None,
+    };
+    let mut exit_code = 0;
+    let result = unsafe { GetExitCodeProcess(handle, &mut exit_code) };
+    let _ = unsafe { CloseHandle(handle) };
+    result.ok()?;
+    Some(exit_code == STILL_ACTIVE.0 as u32)
+}
+
+/// Checks whether a process with the given id is running.
+///
+/// Returns [`None`] if the check is unsupported or fails for other reasons.
+#[cfg(unix)]
+pub fn is_process_alive(process_id: u32) -> Option<bool> {
+    let pid = libc::pid_t::try_from(process_id)
+        .ok()
+        .filter(|pid| *pid > 0)?;
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return Some(true);
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(libc::ESRCH) => Some(false),
+        Some(libc::EPERM) => Some(true),
+        _ => None,
+    }
+}
+
+/// Checks whether a process with the given id is running.
+///
+/// Returns [`None`] if the check is unsupported or fails for other reasons.
+#[cfg(not(any(windows, unix)))]
+pub fn is_process_alive(_process_id: u32) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mem;
+
+    #[test]
+    fn not_started() {
+        let mut liveness = Liveness::new();
+        let start = Instant::now();
+        let state = liveness.update_at(&LinkedMem::default(), start);
+        assert_eq!(state, LivenessState::NotStarted);
+    }
+
+    #[test]
+    fn stale_without_process_check() {
+        let mut liveness = Liveness::new().with_process_check(false);
+        let start = Instant::now();
+        let stale = mem(1234, 42);
+        for (millis, expected) in [
+            (0, LivenessState::Paused),
+            (100, LivenessState::Paused),
+            (600, LivenessState::Paused),
+            (59_000, LivenessState::Paused),
+            (61_000, LivenessState::Exited),
+        ] {
+            let now = start + Duration::from_millis(millis);
+            assert_eq!(liveness.update_at(&stale, now), expected, "at {millis}ms");
+        }
+        assert!(!liveness.has_advanced());
+    }
+
+    #[test]
+    #[cfg(any(windows, unix))]
+    fn stale_with_dead_process() {
+        let mut liveness = Liveness::new().with_process_check(true);
+        let start = Instant::now();
+        let stale = mem(1234, i32::MAX as u32);
+        assert_eq!(liveness.update_at(&stale, start), LivenessState::Exited);
+    }
+
+    #[test]
+    fn running_and_paused() {
+        let mut liveness = Liveness::new().with_process_check(false);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        assert_eq!(
+            liveness.update_at(&mem(1, 42), at(0)),
+            LivenessState::Paused
+        );
+        assert_eq!(
+            liveness.update_at(&mem(2, 42), at(20)),
+            LivenessState::Running
+        );
+        assert_eq!(
+            liveness.update_at(&mem(2, 42), at(200)),
+            LivenessState::Running
+        );
+        assert_eq!(
+            liveness.update_at(&mem(2, 42), at(1000)),
+            LivenessState::Paused
+        );
+        assert_eq!(
+            liveness.update_at(&mem(3, 42), at(5000)),
+            LivenessState::Running
+        );
+        assert_eq!(
+            liveness.update_at(&mem(3, 42), at(70_000)),
+            LivenessState::Exited
+        );
+    }
+
+    #[test]
+    fn new_process_resets() {
+        let mut liveness = Liveness::new().with_process_check(false);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        liveness.update_at(&mem(1, 42), at(0));
+        assert_eq!(
+            liveness.update_at(&mem(2, 42), at(20)),
+            LivenessState::Running
+        );
+        assert_eq!(
+            liveness.update_at(&mem(2, 43), at(40)),
+            LivenessState::Paused
+        );
+        assert_eq!(liveness.process_id(), 43);
+        assert!(!liveness.has_advanced());
+        assert_eq!(
+            liveness.update_at(&mem(3, 43), at(60)),
+            LivenessState::Running
+        );
+    }
+}
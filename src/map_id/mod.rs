@@ -1,4 +1,10 @@
 //! Known map ids.
+//!
+//! Information about known maps can be retrieved via [`lookup`] and [`lookup_name`].
+
+mod registry;
+
+pub use self::registry::*;
 
 /// Raids.
 pub mod raid;
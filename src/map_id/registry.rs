@@ -0,0 +1,176 @@
+use super::{activity, fractal, hub, pvp, raid, strike, wvw};
+
+/// Information about a known map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapInfo {
+    /// Map id.
+    pub id: u32,
+
+    /// Map name.
+    pub name: &'static str,
+
+    /// Map category.
+    pub category: Category,
+}
+
+/// Category of a known map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Raid with wing number.
+    ///
+    /// Wing is [`None`] for maps like the Special Forces Training Area.
+    Raid { wing: Option<u8> },
+
+    /// Fractal of the Mists.
+    Fractal,
+
+    /// Strike Mission with release.
+    Strike(StrikeGroup),
+
+    /// Structured PvP.
+    Pvp,
+
+    /// World versus World.
+    Wvw,
+
+    /// Activity.
+    Activity,
+
+    /// Player hub, including lobbies like the Aerodrome, Mistlock Observatory and PvP Lobby.
+    Hub,
+}
+
+/// Release of a Strike Mission.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum::Display, strum::AsRefStr,
+)]
+pub enum StrikeGroup {
+    Wintersday,
+    IcebroodSaga,
+    EndOfDragons,
+    LivingWorldSeason1,
+    SecretsOfTheObscure,
+}
+
+/// Looks up information about a known map id.
+pub fn lookup(id: u32) -> Option<&'static MapInfo> {
+    MAPS.iter().find(|map| map.id == id)
+}
+
+/// Looks up information about a known map by name.
+///
+/// Names are compared case-insensitively.
+/// Returns the first match for maps with multiple ids.
+pub fn lookup_name(name: &str) -> Option<&'static MapInfo> {
+    MAPS.iter().find(|map| map.name.eq_ignore_ascii_case(name))
+}
+
+/// Returns information about all known maps.
+#[inline]
+pub fn all() -> &'static [MapInfo] {
+    MAPS
+}
+
+macro_rules! maps {
+    ( $( $id:path => $name:literal, $category:expr; )* ) => {
+        &[ $( MapInfo { id: $id, name: $name, category: $category } ),* ]
+    };
+}
+
+static MAPS: &[MapInfo] = maps! {
+    raid::AERODROME => "Lion's Arch Aerodrome", Category::Hub;
+    raid::TRAINING_AREA => "Special Forces Training Area", Category::Raid { wing: None };
+    raid::SPIRIT_VALE => "Spirit Vale", Category::Raid { wing: Some(1) };
+    raid::SALVATION_PASS => "Salvation Pass", Category::Raid { wing: Some(2) };
+    raid::STRONGHOLD_OF_THE_FAITHFUL => "Stronghold of the Faithful", Category::Raid { wing: Some(3) };
+    raid::BASTION_OF_THE_PENITENT => "Bastion of the Penitent", Category::Raid { wing: Some(4) };
+    raid::HALL_OF_CHAINS => "Hall of Chains", Category::Raid { wing: Some(5) };
+    raid::MYTHWRIGHT_GAMBIT => "Mythwright Gambit", Category::Raid { wing: Some(6) };
+    raid::KEY_OF_AHDASHIM => "The Key of Ahdashim", Category::Raid { wing: Some(7) };
+    raid::MOUNT_BALRIOR => "Mount Balrior", Category::Raid { wing: Some(8) };
+
+    fractal::MISTLOCK_OBSERVATORY => "Mistlock Observatory", Category::Hub;
+    fractal::UNCATEGORIZED => "Uncategorized Fractal", Category::Fractal;
+    fractal::SNOWBLIND => "Snowblind Fractal", Category::Fractal;
+    fractal::SWAMPLAND => "Swampland Fractal", Category::Fractal;
+    fractal::URBAN_BATTLEGROUND => "Urban Battleground Fractal", Category::Fractal;
+    fractal::AQUATIC_RUINS => "Aquatic Ruins Fractal", Category::Fractal;
+    fractal::CLIFFSIDE => "Cliffside Fractal", Category::Fractal;
+    fractal::UNDERGROUND_FACILITY => "Underground Facility Fractal", Category::Fractal;
+    fractal::VOLCANIC => "Volcanic Fractal", Category::Fractal;
+    fractal::MOLTEN_FURNANCE => "Molten Furnace Fractal", Category::Fractal;
+    fractal::AETHERBLADE => "Aetherblade Fractal", Category::Fractal;
+    fractal::THAUMANOVA_REACTOR => "Thaumanova Reactor Fractal", Category::Fractal;
+    fractal::SOLID_OCEAN => "Solid Ocean Fractal", Category::Fractal;
+    fractal::MOLTEN_BOSS => "Molten Boss Fractal", Category::Fractal;
+    fractal::CAPTAIN_MAI_TRIN_BOSS => "Captain Mai Trin Boss Fractal", Category::Fractal;
+    fractal::CHAOS => "Chaos Fractal", Category::Fractal;
+    fractal::NIGHTMARE => "Nightmare Fractal", Category::Fractal;
+    fractal::SHATTERED_OBSERVATORY => "Shattered Observatory Fractal", Category::Fractal;
+    fractal::TWILIGHT_OASIS => "Twilight Oasis Fractal", Category::Fractal;
+    fractal::DEEPSTONE => "Deepstone Fractal", Category::Fractal;
+    fractal::SIRENS_REEF => "Siren's Reef Fractal", Category::Fractal;
+    fractal::SUNQUA_PEAK => "Sunqua Peak Fractal", Category::Fractal;
+    fractal::SILENT_SURF => "Silent Surf Fractal", Category::Fractal;
+    fractal::LONELY_TOWER => "Lonely Tower Fractal", Category::Fractal;
+
+    strike::wintersday::SECRET_LAIR_OF_THE_SNOWMEN => "Secret Lair of the Snowmen", Category::Strike(StrikeGroup::Wintersday);
+    strike::ibs::SHIVERPEAKS_PASS => "Shiverpeaks Pass", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::BONESKINNER => "Boneskinner", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::FRAENIR_OF_JORMAG => "Fraenir of Jormag", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::VOICE_AND_CLAW => "Voice of the Fallen and Claw of the Fallen", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::WHISPER_OF_JORMAG => "Whisper of Jormag", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::FORGING_STEEL => "Forging Steel", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::ibs::COLD_WAR => "Cold War", Category::Strike(StrikeGroup::IcebroodSaga);
+    strike::eod::AETHERBLADE_HIDEOUT => "Aetherblade Hideout", Category::Strike(StrikeGroup::EndOfDragons);
+    strike::eod::XUNLAI_JADE_JUNKYARD => "Xunlai Jade Junkyard", Category::Strike(StrikeGroup::EndOfDragons);
+    strike::eod::KAINENG_OVERLOOK => "Kaineng Overlook", Category::Strike(StrikeGroup::EndOfDragons);
+    strike::eod::HARVEST_TEMPLE => "Harvest Temple", Category::Strike(StrikeGroup::EndOfDragons);
+    strike::ls1::OLD_LIONS_COURT => "Old Lion's Court", Category::Strike(StrikeGroup::LivingWorldSeason1);
+    strike::soto::COSMIC_OBSERVATORY => "Cosmic Observatory", Category::Strike(StrikeGroup::SecretsOfTheObscure);
+    strike::soto::TEMPLE_OF_FEBE => "Temple of Febe", Category::Strike(StrikeGroup::SecretsOfTheObscure);
+
+    pvp::PVP_LOBBY => "PvP Lobby", Category::Hub;
+    pvp::BATTLE_OF_KHYLO => "Battle of Khylo", Category::Pvp;
+    pvp::FOREST_OF_NIFLHEL => "Forest of Niflhel", Category::Pvp;
+    pvp::LEGACY_OF_THE_FOEFIRE => "Legacy of the Foefire", Category::Pvp;
+    pvp::TEMPLE_OF_THE_SILENT_STORM => "Temple of the Silent Storm", Category::Pvp;
+    pvp::SPIRIT_WATCH => "Spirit Watch", Category::Pvp;
+    pvp::SKYHAMMER => "Skyhammer", Category::Pvp;
+    pvp::COURTYARD_A => "Courtyard", Category::Pvp;
+    pvp::BATTLE_OF_CHAMPIONSDUSK => "Battle of Champion's Dusk", Category::Pvp;
+    pvp::REVENGE_OF_THE_CAPRICORN => "Revenge of the Capricorn", Category::Pvp;
+    pvp::ETERNAL_COLISEUM => "Eternal Coliseum", Category::Pvp;
+    pvp::HALL_OF_THE_MISTS => "Hall of the Mists", Category::Pvp;
+    pvp::ASURA_ARENA => "Asura Arena", Category::Pvp;
+    pvp::COURTYARD_B => "Courtyard", Category::Pvp;
+    pvp::DJINNS_DOMINION => "Djinn's Dominion", Category::Pvp;
+    pvp::AURIC_SPAN => "Auric Span", Category::Pvp;
+
+    wvw::ETERNAL_BATTLEGROUNS => "Eternal Battlegrounds", Category::Wvw;
+    wvw::ALPINE_BORDERLANDS_GREEN => "Green Alpine Borderlands", Category::Wvw;
+    wvw::ALPINE_BORDERLANDS_BLUE => "Blue Alpine Borderlands", Category::Wvw;
+    wvw::OBSIDIAN_SANCTUM => "Obsidian Sanctum", Category::Wvw;
+    wvw::EDGE_OF_THE_MISTS => "Edge of the Mists", Category::Wvw;
+    wvw::DESERT_BORDERLANDS => "Red Desert Borderlands", Category::Wvw;
+
+    activity::KEG_BRAWL => "Keg Brawl", Category::Activity;
+    activity::REAPERS_RUMBLE => "Reaper's Rumble", Category::Activity;
+    activity::LUNATIC_INQUISITION => "Lunatic Inquisition", Category::Activity;
+    activity::CLOCK_TOWER => "Clock Tower", Category::Activity;
+    activity::SNOWBALL_MAYHEM => "Snowball Mayhem", Category::Activity;
+    activity::TOYPOCALYPSE => "Toypocalypse", Category::Activity;
+    activity::BELL_CHOIR_ENSEMBLE => "Bell Choir Ensemble", Category::Activity;
+    activity::WINTER_WONTERLAND => "Winter Wonderland", Category::Activity;
+    activity::CRAB_TOSS => "Crab Toss", Category::Activity;
+    activity::DRAGON_BALL => "Dragon Ball", Category::Activity;
+    activity::ASPECT_ARENA => "Aspect Arena", Category::Activity;
+    activity::SANCTUM_SPRINT => "Sanctum Sprint", Category::Activity;
+    activity::SOUTHSUN_SURVIVAL => "Southsun Survival", Category::Activity;
+
+    hub::LIONS_ARCH => "Lion's Arch", Category::Hub;
+    hub::MISTLOCK_SANCTUARY => "Mistlock Sanctuary", Category::Hub;
+    hub::EYE_OF_THE_NORTH => "Eye of the North", Category::Hub;
+    hub::ARBORSTONE => "Arborstone", Category::Hub;
+    hub::WIZARDS_TOWER => "Wizard's Tower", Category::Hub;
+};
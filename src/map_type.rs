@@ -2,6 +2,8 @@
 //!
 //! For example [`map_type::INSTANCE`](self::INSTANCE) holds the map type id for instances.
 
+use crate::util::{display_unknown, serde_primitive};
+
 pub const AUTO_REDIRECT: u32 = 0;
 
 pub const CHARACTER_CREATION: u32 = 1;
@@ -43,3 +45,114 @@ pub const BIG_BATTLE: u32 = 17;
 
 /// Armistice Bastion.
 pub const WVW_LOUNGE: u32 = 18;
+
+/// Map type.
+///
+/// Typed version of the constants in the [`map_type`](self) module.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    strum::AsRefStr,
+    num_enum::FromPrimitive,
+    num_enum::IntoPrimitive,
+)]
+#[repr(u32)]
+pub enum MapType {
+    AutoRedirect = AUTO_REDIRECT,
+    CharacterCreation = CHARACTER_CREATION,
+    Pvp = PVP,
+    Gvg = GVG,
+    Instance = INSTANCE,
+    Pve = PVE,
+    Tournament = TOURNAMENT,
+    Tutorial = TUTORIAL,
+    UserTournament = USER_TOURNAMENT,
+    WvwEternalBattlegrounds = WVW_ETERNAL_BATTLEGROUNDS,
+    WvwBlueBorderlands = WVW_BLUE_BORDERLANDS,
+    WvwGreenBorderlands = WVW_GREEN_BORDERLANDS,
+    WvwRedBorderlands = WVW_RED_BORDERLANDS,
+
+    /// Fortune's Vale.
+    WvwReward = WVW_REWARD,
+
+    WvwObsidianSanctum = WVW_OBSIDIAN_SANCTUM,
+    WvwEdgeOfTheMists = WVW_EDGE_OF_THE_MISTS,
+
+    /// Mini maps like Mistlock Sanctuary, Aerodrome, etc.
+    PveMini = PVE_MINI,
+
+    /// PvP 15v15.
+    BigBattle = BIG_BATTLE,
+
+    /// Armistice Bastion.
+    WvwLounge = WVW_LOUNGE,
+
+    /// Map type unknown to this version of the crate.
+    #[num_enum(catch_all)]
+    Unknown(u32),
+}
+
+impl MapType {
+    /// Whether the map type is part of World versus World, including the Armistice Bastion lounge.
+    #[inline]
+    pub fn is_wvw(&self) -> bool {
+        matches!(
+            self,
+            Self::WvwEternalBattlegrounds
+                | Self::WvwBlueBorderlands
+                | Self::WvwGreenBorderlands
+                | Self::WvwRedBorderlands
+                | Self::WvwReward
+                | Self::WvwObsidianSanctum
+                | Self::WvwEdgeOfTheMists
+                | Self::WvwLounge
+        )
+    }
+
+    /// Whether the map type is part of PvP.
+    #[inline]
+    pub fn is_pvp(&self) -> bool {
+        matches!(
+            self,
+            Self::Pvp | Self::Gvg | Self::Tournament | Self::UserTournament | Self::BigBattle
+        )
+    }
+
+    /// Whether the map type is an instance, for example a dungeon, raid, fractal or story instance.
+    #[inline]
+    pub fn is_instance(&self) -> bool {
+        matches!(self, Self::Instance)
+    }
+
+    /// Whether the map type is a competitive mode, meaning PvP or World versus World outside of the lounge.
+    #[inline]
+    pub fn is_competitive(&self) -> bool {
+        self.is_pvp() || (self.is_wvw() && *self != Self::WvwLounge)
+    }
+
+    /// Whether the map type is a lobby, like character selection, the Armistice Bastion or mini maps.
+    #[inline]
+    pub fn is_lobby(&self) -> bool {
+        matches!(
+            self,
+            Self::CharacterCreation | Self::WvwLounge | Self::PveMini
+        )
+    }
+}
+
+// num_enum treats #[default] as fallback variant, which conflicts with the catch-all `Unknown`
+impl Default for MapType {
+    #[inline]
+    fn default() -> Self {
+        Self::AutoRedirect
+    }
+}
+
+serde_primitive!(MapType => u32);
+display_unknown!(MapType);
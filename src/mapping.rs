@@ -0,0 +1,164 @@
+use crate::{Error, LinkedMem};
+use std::{ffi::CString, io, mem, ptr::NonNull};
+#[cfg(unix)]
+use std::{
+    fs::{File, OpenOptions},
+    os::fd::{AsRawFd, FromRawFd},
+    path::Path,
+};
+#[cfg(windows)]
+use windows::{
+    core::{Free, PCSTR},
+    Win32::{
+        Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE},
+        System::Memory::{
+            CreateFileMappingA, MapViewOfFile, FILE_MAP_READ, FILE_MAP_WRITE, PAGE_READWRITE,
+        },
+    },
+};
+
+/// Size of the MumbleLink memory mapping.
+const SIZE: usize = mem::size_of::<LinkedMem>();
+
+/// Platform memory mapping of a [`LinkedMem`].
+#[derive(Debug)]
+pub struct Mapping {
+    #[cfg(windows)]
+    handle: HANDLE,
+    ptr: NonNull<LinkedMem>,
+}
+
+impl Mapping {
+    /// Returns the pointer to the mapped [`LinkedMem`].
+    #[inline]
+    pub fn as_non_null(&self) -> NonNull<LinkedMem> {
+        self.ptr
+    }
+}
+
+#[cfg(windows)]
+impl Mapping {
+    /// Opens the named file mapping, creating it if necessary.
+    pub fn open(name: &str, write: bool) -> Result<Self, Error> {
+        if name == "0" {
+            return Err(Error::Disabled);
+        }
+        let name = CString::new(name)?;
+
+        let handle = unsafe {
+            CreateFileMappingA(
+                INVALID_HANDLE_VALUE,
+                None,
+                PAGE_READWRITE,
+                0,
+                SIZE as u32,
+                PCSTR::from_raw(name.as_ptr().cast()),
+            )?
+        };
+
+        let access = if write { FILE_MAP_WRITE } else { FILE_MAP_READ };
+        let ptr = unsafe { MapViewOfFile(handle, access, 0, 0, SIZE) }.Value;
+        if let Some(ptr) = NonNull::new(ptr.cast()) {
+            Ok(Self { handle, ptr })
+        } else {
+            let err = io::Error::last_os_error();
+            let _ = unsafe { CloseHandle(handle) };
+            Err(err.into())
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Mapping {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { self.handle.free() }
+    }
+}
+
+#[cfg(unix)]
+impl Mapping {
+    /// Opens the named POSIX shared memory object or the file if the name contains a `/`.
+    ///
+    /// When writing, the object is created and resized if necessary.
+    pub fn open(name: &str, write: bool) -> Result<Self, Error> {
+        if name == "0" {
+            return Err(Error::Disabled);
+        }
+        if name.contains('/') {
+            return Self::open_path(name, write);
+        }
+        let name = CString::new(format!("/{name}"))?;
+
+        let flags = if write {
+            libc::O_RDWR | libc::O_CREAT
+        } else {
+            libc::O_RDONLY
+        };
+        let fd = unsafe { libc::shm_open(name.as_ptr(), flags, 0o666) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        let file = unsafe { File::from_raw_fd(fd) };
+        Self::map_file(&file, write)
+    }
+
+    /// Opens the file at the given path.
+    ///
+    /// When writing, the file is created and resized if necessary.
+    pub fn open_path(path: impl AsRef<Path>, write: bool) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .create(write)
+            .truncate(false)
+            .open(path)?;
+        Self::map_file(&file, write)
+    }
+
+    /// Maps the given file.
+    fn map_file(file: &File, write: bool) -> Result<Self, Error> {
+        // mapping beyond the end of the file would fault on access
+        let size = file.metadata()?.len();
+        if size < SIZE as u64 {
+            if write {
+                file.set_len(SIZE as u64)?;
+            } else {
+                return Err(Error::TooSmall(size));
+            }
+        }
+
+        let prot = if write {
+            libc::PROT_READ | libc::PROT_WRITE
+        } else {
+            libc::PROT_READ
+        };
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                SIZE,
+                prot,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error().into());
+        }
+        let ptr = NonNull::new(ptr.cast()).expect("mmap returned null");
+        Ok(Self { ptr })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { libc::munmap(self.ptr.as_ptr().cast(), SIZE) };
+    }
+}
+
+unsafe impl Send for Mapping {}
+
+unsafe impl Sync for Mapping {}
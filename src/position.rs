@@ -0,0 +1,129 @@
+use crate::{
+    math::{length, sub},
+    LinkedMem, Position,
+};
+
+impl Position {
+    /// Creates a new position from the position, front and top vectors.
+    ///
+    /// Accepts any type convertible to `[f32; 3]`, for example `glam` or `mint` vectors.
+    #[inline]
+    pub fn new(
+        position: impl Into<[f32; 3]>,
+        front: impl Into<[f32; 3]>,
+        top: impl Into<[f32; 3]>,
+    ) -> Self {
+        Self {
+            position: position.into(),
+            front: front.into(),
+            top: top.into(),
+        }
+    }
+
+    /// Returns the yaw of the front vector in radians.
+    ///
+    /// `0` is facing north along `z`, positive values turn clockwise towards east along `x`.
+    /// In the range `-π..=π`.
+    #[inline]
+    pub fn yaw(&self) -> f32 {
+        self.front[0].atan2(self.front[2])
+    }
+
+    /// Returns the compass heading of the front vector in degrees.
+    ///
+    /// `0` is north, `90` is east. In the range `0..360`.
+    #[inline]
+    pub fn heading(&self) -> f32 {
+        self.yaw().to_degrees().rem_euclid(360.0)
+    }
+
+    /// Returns the pitch of the front vector in radians.
+    ///
+    /// Positive values look up, negative values look down.
+    /// In the range `-π/2..=π/2`, `0` for a zero front vector.
+    #[inline]
+    pub fn pitch(&self) -> f32 {
+        let length = length(self.front);
+        if length > 0.0 {
+            (self.front[1] / length).clamp(-1.0, 1.0).asin()
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the 3D distance to the other position.
+    #[inline]
+    pub fn distance(&self, other: &Position) -> f32 {
+        self.distance_to(other.position)
+    }
+
+    /// Returns the 3D distance to the point.
+    #[inline]
+    pub fn distance_to(&self, point: [f32; 3]) -> f32 {
+        length(sub(point, self.position))
+    }
+
+    /// Returns the horizontal distance to the point, ignoring the height.
+    #[inline]
+    pub fn horizontal_distance_to(&self, point: [f32; 3]) -> f32 {
+        let [x, _, z] = sub(point, self.position);
+        x.hypot(z)
+    }
+}
+
+impl LinkedMem {
+    /// Returns the 3D distance between the player avatar and the camera.
+    #[inline]
+    pub fn camera_distance(&self) -> f32 {
+        self.avatar.distance(&self.camera)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn facing(front: [f32; 3]) -> Position {
+        Position::new([0.0; 3], front, [0.0, 1.0, 0.0])
+    }
+
+    #[test]
+    fn yaw() {
+        for (front, yaw, heading) in [
+            ([0.0, 0.0, 1.0], 0.0, 0.0),
+            ([1.0, 0.0, 0.0], FRAC_PI_2, 90.0),
+            ([0.0, 0.0, -1.0], PI, 180.0),
+            ([-1.0, 0.0, 0.0], -FRAC_PI_2, 270.0),
+            ([1.0, 5.0, 1.0], FRAC_PI_4, 45.0),
+        ] {
+            let position = facing(front);
+            assert_eq!(position.yaw(), yaw, "{front:?}");
+            assert_eq!(position.heading(), heading, "{front:?}");
+        }
+    }
+
+    #[test]
+    fn pitch() {
+        for (front, pitch) in [
+            ([0.0, 0.0, 1.0], 0.0),
+            ([0.0, 1.0, 0.0], FRAC_PI_2),
+            ([0.0, -3.0, 0.0], -FRAC_PI_2),
+            ([1.0, 1.0, 0.0], FRAC_PI_4),
+            ([0.0, 1.0, 1e-30], FRAC_PI_2),
+            ([0.0; 3], 0.0),
+        ] {
+            let actual = facing(front).pitch();
+            assert!((actual - pitch).abs() < 1e-6, "{front:?}: {actual}");
+        }
+        let pitch = facing([1e-30, 1e30, 0.0]).pitch();
+        assert!(pitch.is_finite());
+    }
+
+    #[test]
+    fn distance() {
+        let position = Position::new([1.0, 2.0, 3.0], [0.0; 3], [0.0; 3]);
+        assert_eq!(position.distance_to([4.0, 6.0, 3.0]), 5.0);
+        assert_eq!(position.horizontal_distance_to([4.0, 100.0, 7.0]), 5.0);
+    }
+}
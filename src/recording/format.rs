@@ -0,0 +1,518 @@
+use crate::{until_nul, LinkedMem, Position, RawContext, RecordingError};
+use bitflags::bitflags;
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    time::Duration,
+};
+
+/// Magic bytes at the start of a recording.
+pub const MAGIC: [u8; 4] = *b"GW2M";
+
+/// Version of the recording format.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Header at the start of a recording.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Header {
+    /// Version of the crate writing the recording.
+    pub crate_version: String,
+
+    /// Build id of the game at the start of the recording.
+    pub build_id: u32,
+}
+
+impl Header {
+    /// Creates a new header for the current crate version.
+    #[inline]
+    pub fn new(build_id: u32) -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").into(),
+            build_id,
+        }
+    }
+
+    pub(crate) fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let version = self.crate_version.as_bytes();
+        let len = u8::try_from(version.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "crate version too long"))?;
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&[len])?;
+        writer.write_all(version)?;
+        writer.write_all(&self.build_id.to_le_bytes())
+    }
+
+    pub(crate) fn read_from(reader: &mut impl Read) -> Result<Self, RecordingError> {
+        if read_array::<4>(reader)? != MAGIC {
+            return Err(RecordingError::InvalidHeader);
+        }
+        let version = u16::from_le_bytes(read_array(reader)?);
+        if version != FORMAT_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+        let [len] = read_array(reader)?;
+        let mut crate_version = vec![0; len.into()];
+        reader.read_exact(&mut crate_version)?;
+        let crate_version =
+            String::from_utf8(crate_version).map_err(|_| RecordingError::InvalidHeader)?;
+        let build_id = u32::from_le_bytes(read_array(reader)?);
+        Ok(Self {
+            crate_version,
+            build_id,
+        })
+    }
+}
+
+/// Recorded [`LinkedMem`] contents with timestamp.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Time since the start of the recording.
+    pub timestamp: Duration,
+
+    /// [`LinkedMem`] contents at the time.
+    pub mem: LinkedMem,
+}
+
+bitflags! {
+    /// Fields stored in a frame in addition to `ui_tick`, `avatar` and `camera`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct Changes: u8 {
+        const META = 0b1;
+
+        const NAME = 0b10;
+
+        const IDENTITY = 0b100;
+
+        const CONTEXT = 0b1000;
+
+        const DESCRIPTION = 0b10000;
+    }
+}
+
+impl Changes {
+    /// Returns the fields changed between the previous and the given [`LinkedMem`].
+    pub fn between(prev: Option<&LinkedMem>, mem: &LinkedMem) -> Self {
+        let Some(prev) = prev else {
+            return Self::all();
+        };
+        let mut changes = Self::empty();
+        changes.set(
+            Self::META,
+            prev.ui_version != mem.ui_version || prev.context_len != mem.context_len,
+        );
+        changes.set(Self::NAME, until_nul(&prev.name) != until_nul(&mem.name));
+        changes.set(
+            Self::IDENTITY,
+            until_nul(&prev.identity) != until_nul(&mem.identity),
+        );
+        changes.set(
+            Self::CONTEXT,
+            encode_context(&prev.context) != encode_context(&mem.context),
+        );
+        changes.set(
+            Self::DESCRIPTION,
+            until_nul(&prev.description) != until_nul(&mem.description),
+        );
+        changes
+    }
+}
+
+/// Writes a frame with the fields changed since the previous [`LinkedMem`].
+pub(crate) fn write_frame(
+    writer: &mut impl Write,
+    timestamp: Duration,
+    prev: Option<&LinkedMem>,
+    mem: &LinkedMem,
+) -> io::Result<()> {
+    let changes = Changes::between(prev, mem);
+    let micros = u64::try_from(timestamp.as_micros()).unwrap_or(u64::MAX);
+    writer.write_all(&micros.to_le_bytes())?;
+    writer.write_all(&[changes.bits()])?;
+    writer.write_all(&mem.ui_tick.to_le_bytes())?;
+    write_position(writer, &mem.avatar)?;
+    write_position(writer, &mem.camera)?;
+    if changes.contains(Changes::META) {
+        writer.write_all(&mem.ui_version.to_le_bytes())?;
+        writer.write_all(&mem.context_len.to_le_bytes())?;
+    }
+    if changes.contains(Changes::NAME) {
+        write_wide(writer, &mem.name)?;
+    }
+    if changes.contains(Changes::IDENTITY) {
+        write_wide(writer, &mem.identity)?;
+    }
+    if changes.contains(Changes::CONTEXT) {
+        writer.write_all(&encode_context(&mem.context))?;
+    }
+    if changes.contains(Changes::DESCRIPTION) {
+        write_wide(writer, &mem.description)?;
+    }
+    Ok(())
+}
+
+/// Reads the next frame into the current [`LinkedMem`] and returns its timestamp.
+///
+/// Returns [`None`] if the end of the recording was reached.
+pub(crate) fn read_frame(
+    reader: &mut impl Read,
+    mem: &mut LinkedMem,
+) -> Result<Option<Duration>, RecordingError> {
+    let mut micros = [0; 8];
+    let mut filled = 0;
+    while filled < micros.len() {
+        match reader.read(&mut micros[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::Error::from(ErrorKind::UnexpectedEof).into()),
+            Ok(len) => filled += len,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    let timestamp = Duration::from_micros(u64::from_le_bytes(micros));
+
+    let [bits] = read_array(reader)?;
+    let changes = Changes::from_bits(bits).ok_or(RecordingError::InvalidFrame)?;
+    mem.ui_tick = u32::from_le_bytes(read_array(reader)?);
+    mem.avatar = read_position(reader)?;
+    mem.camera = read_position(reader)?;
+    if changes.contains(Changes::META) {
+        mem.ui_version = u32::from_le_bytes(read_array(reader)?);
+        mem.context_len = u32::from_le_bytes(read_array(reader)?);
+    }
+    if changes.contains(Changes::NAME) {
+        read_wide(reader, &mut mem.name)?;
+    }
+    if changes.contains(Changes::IDENTITY) {
+        read_wide(reader, &mut mem.identity)?;
+    }
+    if changes.contains(Changes::CONTEXT) {
+        mem.context = decode_context(&read_array(reader)?);
+    }
+    if changes.contains(Changes::DESCRIPTION) {
+        read_wide(reader, &mut mem.description)?;
+    }
+    Ok(Some(timestamp))
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buffer = [0; N];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn write_position(writer: &mut impl Write, pos: &Position) -> io::Result<()> {
+    for value in pos.position.iter().chain(&pos.front).chain(&pos.top) {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_position(reader: &mut impl Read) -> io::Result<Position> {
+    let mut pos = Position::default();
+    for value in pos
+        .position
+        .iter_mut()
+        .chain(&mut pos.front)
+        .chain(&mut pos.top)
+    {
+        *value = f32::from_le_bytes(read_array(reader)?);
+    }
+    Ok(pos)
+}
+
+fn write_wide<const N: usize>(writer: &mut impl Write, wide: &[u16; N]) -> io::Result<()> {
+    let units = until_nul(wide);
+    // buffers hold at most 2048 units
+    writer.write_all(&(units.len() as u16).to_le_bytes())?;
+    for unit in units {
+        writer.write_all(&unit.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_wide<const N: usize>(
+    reader: &mut impl Read,
+    wide: &mut [u16; N],
+) -> Result<(), RecordingError> {
+    let len = u16::from_le_bytes(read_array(reader)?).into();
+    if len > N {
+        return Err(RecordingError::InvalidFrame);
+    }
+    *wide = [0; N];
+    for unit in &mut wide[..len] {
+        *unit = u16::from_le_bytes(read_array(reader)?);
+    }
+    Ok(())
+}
+
+/// Size of the encoded [`RawContext`] without padding.
+const CONTEXT_SIZE: usize = 85;
+
+fn encode_context(context: &RawContext) -> [u8; CONTEXT_SIZE] {
+    let mut bytes = [0; CONTEXT_SIZE];
+    let mut writer = bytes.as_mut_slice();
+    let fields: [&[u8]; 17] = [
+        &context.server_address,
+        &context.map_id.to_le_bytes(),
+        &context.map_type.to_le_bytes(),
+        &context.shard_id.to_le_bytes(),
+        &context.instance.to_le_bytes(),
+        &context.build_id.to_le_bytes(),
+        &context.ui_state.to_le_bytes(),
+        &context.compass_width.to_le_bytes(),
+        &context.compass_height.to_le_bytes(),
+        &context.compass_rotation.to_le_bytes(),
+        &context.player_x.to_le_bytes(),
+        &context.player_y.to_le_bytes(),
+        &context.map_center_x.to_le_bytes(),
+        &context.map_center_y.to_le_bytes(),
+        &context.map_scale.to_le_bytes(),
+        &context.process_id.to_le_bytes(),
+        &[context.mount_index],
+    ];
+    for field in fields {
+        writer.write_all(field).expect("context size mismatch");
+    }
+    bytes
+}
+
+fn decode_context(bytes: &[u8; CONTEXT_SIZE]) -> RawContext {
+    let mut reader = bytes.as_slice();
+    let mut next = || read_array::<4>(&mut reader).expect("context size mismatch");
+    let server_address = {
+        let mut address = [0; 28];
+        for chunk in address.chunks_exact_mut(4) {
+            chunk.copy_from_slice(&next());
+        }
+        address
+    };
+    let map_id = u32::from_le_bytes(next());
+    let map_type = u32::from_le_bytes(next());
+    let shard_id = u32::from_le_bytes(next());
+    let instance = u32::from_le_bytes(next());
+    let build_id = u32::from_le_bytes(next());
+    let ui_state = u32::from_le_bytes(next());
+    let [w0, w1, h0, h1] = next();
+    let compass_rotation = f32::from_le_bytes(next());
+    let player_x = f32::from_le_bytes(next());
+    let player_y = f32::from_le_bytes(next());
+    let map_center_x = f32::from_le_bytes(next());
+    let map_center_y = f32::from_le_bytes(next());
+    let map_scale = f32::from_le_bytes(next());
+    let process_id = u32::from_le_bytes(next());
+    RawContext {
+        server_address,
+        map_id,
+        map_type,
+        shard_id,
+        instance,
+        build_id,
+        ui_state,
+        compass_width: u16::from_le_bytes([w0, w1]),
+        compass_height: u16::from_le_bytes([h0, h1]),
+        compass_rotation,
+        player_x,
+        player_y,
+        map_center_x,
+        map_center_y,
+        map_scale,
+        process_id,
+        mount_index: bytes[CONTEXT_SIZE - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        recording::{Reader, Recorder},
+        MumblePtr,
+    };
+
+    fn set_wide<const N: usize>(wide: &mut [u16; N], string: &str) {
+        *wide = [0; N];
+        for (unit, char) in wide.iter_mut().zip(string.encode_utf16()) {
+            *unit = char;
+        }
+    }
+
+    fn context(seed: u32) -> RawContext {
+        let mut server_address = [0; 28];
+        for (i, byte) in server_address.iter_mut().enumerate() {
+            *byte = seed as u8 ^ i as u8;
+        }
+        let float = |offset: u32| (seed * 100 + offset) as f32 + 0.25;
+        RawContext {
+            server_address,
+            map_id: seed + 1,
+            map_type: seed + 2,
+            shard_id: seed + 3,
+            instance: seed + 4,
+            build_id: seed + 5,
+            ui_state: seed + 6,
+            compass_width: seed as u16 + 7,
+            compass_height: seed as u16 + 8,
+            compass_rotation: float(9),
+            player_x: float(10),
+            player_y: float(11),
+            map_center_x: float(12),
+            map_center_y: float(13),
+            map_scale: float(14),
+            process_id: seed + 15,
+            mount_index: seed as u8 + 16,
+        }
+    }
+
+    fn frames() -> Vec<LinkedMem> {
+        let mut mem = LinkedMem {
+            ui_version: 2,
+            ui_tick: 1,
+            context_len: 48,
+            context: context(1),
+            ..LinkedMem::default()
+        };
+        set_wide(&mut mem.name, "Guild Wars 2");
+        set_wide(&mut mem.identity, r#"{"name":"First"}"#);
+        set_wide(&mut mem.description, "description");
+        let mut frames = vec![mem.clone()];
+
+        mem.ui_tick = 2;
+        mem.avatar = Position::new([1.0, 2.0, 3.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        mem.camera = Position::new([4.0, 5.0, 6.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        frames.push(mem.clone());
+
+        mem.ui_tick = 3;
+        mem.context = context(2);
+        frames.push(mem.clone());
+
+        mem.ui_tick = 4;
+        set_wide(&mut mem.identity, r#"{"name":"Second, longer name"}"#);
+        set_wide(&mut mem.name, "Guild Wars 2 \u{1f600}");
+        frames.push(mem.clone());
+
+        mem.ui_tick = 5;
+        set_wide(&mut mem.identity, r#"{}"#);
+        mem.context.mount_index = 0;
+        frames.push(mem.clone());
+
+        mem.ui_tick = 6;
+        mem.ui_version = 3;
+        set_wide(&mut mem.description, &"x".repeat(2048));
+        frames.push(mem.clone());
+
+        mem.ui_tick = 7;
+        set_wide(&mut mem.description, "");
+        set_wide(&mut mem.name, "");
+        frames.push(mem);
+
+        frames
+    }
+
+    fn record(frames: &[LinkedMem]) -> Vec<u8> {
+        let mut link = LinkedMem {
+            context: context(7),
+            ..LinkedMem::default()
+        };
+        let ptr = unsafe { MumblePtr::new(&mut link) }.unwrap();
+        let mut recorder = Recorder::new(ptr, Vec::new()).unwrap();
+        for (i, mem) in frames.iter().enumerate() {
+            let timestamp = Duration::from_millis(20 * i as u64);
+            recorder.write_frame(mem.clone(), timestamp).unwrap();
+        }
+        recorder.into_inner()
+    }
+
+    #[test]
+    fn changes() {
+        let frames = frames();
+        let changes: Vec<_> = frames
+            .iter()
+            .enumerate()
+            .map(|(i, mem)| Changes::between(i.checked_sub(1).map(|i| &frames[i]), mem))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                Changes::all(),
+                Changes::empty(),
+                Changes::CONTEXT,
+                Changes::NAME | Changes::IDENTITY,
+                Changes::IDENTITY | Changes::CONTEXT,
+                Changes::META | Changes::DESCRIPTION,
+                Changes::NAME | Changes::DESCRIPTION,
+            ]
+        );
+    }
+
+    #[test]
+    fn context_roundtrip() {
+        let context = context(3);
+        let decoded = decode_context(&encode_context(&context));
+        assert_eq!(format!("{decoded:?}"), format!("{context:?}"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let frames = frames();
+        let data = record(&frames);
+
+        let reader = Reader::new(data.as_slice()).unwrap();
+        assert_eq!(reader.header(), &Header::new(context(7).build_id));
+        let read = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(read.len(), frames.len());
+        for (i, (frame, mem)) in read.iter().zip(&frames).enumerate() {
+            assert_eq!(frame.timestamp, Duration::from_millis(20 * i as u64));
+            assert_eq!(format!("{:?}", frame.mem), format!("{mem:?}"), "frame {i}");
+        }
+    }
+
+    #[test]
+    fn invalid_header() {
+        let mut data = record(&frames());
+        data[0] = b'X';
+        assert!(matches!(
+            Reader::new(data.as_slice()),
+            Err(RecordingError::InvalidHeader)
+        ));
+
+        let mut data = record(&frames());
+        data[4..6].copy_from_slice(&2u16.to_le_bytes());
+        assert!(matches!(
+            Reader::new(data.as_slice()),
+            Err(RecordingError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn invalid_frame() {
+        let header_len = record(&[]).len();
+
+        let mut data = record(&frames()[..1]);
+        data[header_len + 8] = 0xff;
+        let mut reader = Reader::new(data.as_slice()).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(RecordingError::InvalidFrame))
+        ));
+        assert!(reader.next().is_none());
+
+        // name length directly follows ui_tick, avatar and camera after the first frame's meta
+        let mut data = record(&frames()[..1]);
+        let name_len = header_len + 8 + 1 + 4 + 2 * 36 + 8;
+        data[name_len..name_len + 2].copy_from_slice(&257u16.to_le_bytes());
+        let mut reader = Reader::new(data.as_slice()).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(RecordingError::InvalidFrame))
+        ));
+
+        let data = record(&frames()[..2]);
+        let mut reader = Reader::new(&data[..data.len() - 1]).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(RecordingError::IoError(err))) if err.kind() == ErrorKind::UnexpectedEof
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+//! Recording and playback of [`LinkedMem`](crate::LinkedMem) frames.
+//!
+//! Sessions are recorded with a [`Recorder`] and read back frame by frame with a [`Reader`].
+//! A [`Replayer`] plays back a recording by wall clock.
+//!
+//! A recording starts with a [`Header`] holding the crate version and the `build_id` of the game.
+//! It is followed by timestamped frames storing only the fields changed since the previous frame.
+//! The `ui_tick`, `avatar` and `camera` are stored in every frame,
+//! the remaining fields only when changed.
+//! Text buffers are stored until their first `0`.
+//! All values are little-endian.
+
+mod format;
+mod reader;
+mod recorder;
+mod replayer;
+
+pub use self::{
+    format::{Frame, Header, FORMAT_VERSION, MAGIC},
+    reader::*,
+    recorder::*,
+    replayer::*,
+};
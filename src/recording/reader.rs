@@ -0,0 +1,73 @@
+use super::format::{read_frame, Frame, Header};
+use crate::{LinkedMem, RecordingError};
+use std::{io::Read, iter::FusedIterator};
+
+/// Reads [`LinkedMem`] frames from a recording.
+///
+/// Yields a [`Frame`] with the full contents for each recorded frame.
+#[derive(Debug)]
+pub struct Reader<R: Read> {
+    reader: R,
+    header: Header,
+    current: LinkedMem,
+    done: bool,
+}
+
+impl<R: Read> Reader<R> {
+    /// Creates a new reader and reads the [`Header`].
+    pub fn new(mut reader: R) -> Result<Self, RecordingError> {
+        let header = Header::read_from(&mut reader)?;
+        Ok(Self {
+            reader,
+            header,
+            current: LinkedMem::default(),
+            done: false,
+        })
+    }
+
+    /// Returns the [`Header`] of the recording.
+    #[inline]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Reads the next [`Frame`].
+    ///
+    /// Returns [`None`] if the end of the recording was reached.
+    pub fn read_frame(&mut self) -> Result<Option<Frame>, RecordingError> {
+        if self.done {
+            return Ok(None);
+        }
+        match read_frame(&mut self.reader, &mut self.current) {
+            Ok(Some(timestamp)) => Ok(Some(Frame {
+                timestamp,
+                mem: self.current.clone(),
+            })),
+            Ok(None) => {
+                self.done = true;
+                Ok(None)
+            }
+            Err(err) => {
+                self.done = true;
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = Result<Frame, RecordingError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+impl<R: Read> FusedIterator for Reader<R> {}
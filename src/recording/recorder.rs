@@ -0,0 +1,82 @@
+use super::format::{write_frame, Header};
+use crate::{LinkedMem, MumblePtr};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// Records [`LinkedMem`] frames read via a [`MumblePtr`] into a writer.
+///
+/// See the [`recording`](crate::recording) module for the format.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    ptr: MumblePtr,
+    writer: W,
+    start: Instant,
+    previous: Option<LinkedMem>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Creates a new recorder and writes the [`Header`].
+    ///
+    /// The current time is used as start of the recording.
+    pub fn new(ptr: MumblePtr, mut writer: W) -> io::Result<Self> {
+        Header::new(ptr.read_build_id()).write_to(&mut writer)?;
+        Ok(Self {
+            ptr,
+            writer,
+            start: Instant::now(),
+            previous: None,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Returns the time elapsed since the start of the recording.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Reads the current [`LinkedMem`] contents and records them as frame.
+    ///
+    /// Reads are skipped if the `ui_tick` did not advance since the previous frame.
+    /// Returns whether a frame was written.
+    pub fn record(&mut self) -> io::Result<bool> {
+        if self
+            .previous
+            .as_ref()
+            .is_some_and(|prev| prev.ui_tick == self.ptr.read_ui_tick())
+        {
+            return Ok(false);
+        }
+        let mem = self.ptr.read_consistent().into_inner();
+        let timestamp = self.elapsed();
+        self.write_frame(mem, timestamp)?;
+        Ok(true)
+    }
+
+    /// Records the given [`LinkedMem`] contents as frame with the given timestamp.
+    ///
+    /// Timestamps are expected to be increasing.
+    /// The frame is encoded upfront and passed to the writer with a single `write_all`.
+    pub fn write_frame(&mut self, mem: LinkedMem, timestamp: Duration) -> io::Result<()> {
+        self.buffer.clear();
+        write_frame(&mut self.buffer, timestamp, self.previous.as_ref(), &mem)?;
+        self.writer.write_all(&self.buffer)?;
+        self.previous = Some(mem);
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,491 @@
+use super::format::{read_frame, Header};
+#[cfg(any(windows, unix))]
+use crate::MumbleWriter;
+use crate::{LinkSource, LinkedMem, RecordingError};
+use std::{
+    fmt,
+    io::Read,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Number of frames between stored keyframes used for seeking.
+const KEYFRAME_INTERVAL: usize = 256;
+
+/// Plays back a recording by wall clock.
+///
+/// Reads via [`LinkSource`] return the frame at the current playback position,
+/// allowing to use the replayer in place of a live [`MumbleLink`](crate::MumbleLink).
+/// Frames are decoded on demand, only every 256th frame is kept in memory for seeking.
+pub struct Replayer {
+    header: Header,
+    data: Vec<u8>,
+    frames: Vec<FrameIndex>,
+    keyframes: Vec<LinkedMem>,
+    cursor: Mutex<Option<Cursor>>,
+    position: Duration,
+    anchor: Option<Instant>,
+    speed: f64,
+    looping: bool,
+    #[cfg(any(windows, unix))]
+    writer: Option<MumbleWriter>,
+    #[cfg(any(windows, unix))]
+    written: Option<usize>,
+}
+
+/// Location of a frame in the recording.
+#[derive(Debug, Clone, Copy)]
+struct FrameIndex {
+    timestamp: Duration,
+    offset: usize,
+}
+
+/// Most recently decoded frame.
+#[derive(Debug, Clone)]
+struct Cursor {
+    index: usize,
+    mem: LinkedMem,
+}
+
+impl Replayer {
+    /// Creates a new replayer from the bytes of a recording.
+    ///
+    /// The recording is validated upfront.
+    /// Playback starts immediately at normal speed.
+    pub fn new(data: Vec<u8>) -> Result<Self, RecordingError> {
+        let mut reader = data.as_slice();
+        let header = Header::read_from(&mut reader)?;
+
+        let mut frames = Vec::new();
+        let mut keyframes = Vec::new();
+        let mut mem = LinkedMem::default();
+        loop {
+            if frames.len() % KEYFRAME_INTERVAL == 0 {
+                keyframes.push(mem.clone());
+            }
+            let offset = data.len() - reader.len();
+            match read_frame(&mut reader, &mut mem)? {
+                Some(timestamp) => frames.push(FrameIndex { timestamp, offset }),
+                None => break,
+            }
+        }
+
+        Ok(Self {
+            header,
+            data,
+            frames,
+            keyframes,
+            cursor: Mutex::new(None),
+            position: Duration::ZERO,
+            anchor: Some(Instant::now()),
+            speed: 1.0,
+            looping: false,
+            #[cfg(any(windows, unix))]
+            writer: None,
+            #[cfg(any(windows, unix))]
+            written: None,
+        })
+    }
+
+    /// Creates a new replayer reading the entire recording from the reader.
+    #[inline]
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, RecordingError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::new(data)
+    }
+
+    /// Adds a [`MumbleWriter`] the current frame is written to on [`Replayer::update`].
+    ///
+    /// This allows unmodified consumers of the MumbleLink to observe the replayed frames.
+    #[inline]
+    #[cfg(any(windows, unix))]
+    pub fn with_writer(mut self, writer: MumbleWriter) -> Self {
+        self.writer = Some(writer);
+        self.written = None;
+        self
+    }
+
+    /// Returns the [`MumbleWriter`] frames are written to.
+    #[inline]
+    #[cfg(any(windows, unix))]
+    pub fn writer(&self) -> Option<&MumbleWriter> {
+        self.writer.as_ref()
+    }
+
+    /// Returns the [`Header`] of the recording.
+    #[inline]
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the number of frames in the recording.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the timestamp of the last frame.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.frames
+            .last()
+            .map(|frame| frame.timestamp)
+            .unwrap_or_default()
+    }
+
+    /// Returns the current playback position.
+    ///
+    /// Wraps around at the end when looping, otherwise stops at the end.
+    pub fn position(&self) -> Duration {
+        let elapsed = self
+            .anchor
+            .map(|anchor| anchor.elapsed().mul_f64(self.speed))
+            .unwrap_or_default();
+        let position = self.position.saturating_add(elapsed);
+        let duration = self.duration();
+        if self.looping && !duration.is_zero() {
+            let nanos = position.as_nanos() % duration.as_nanos();
+            Duration::from_nanos(nanos as u64)
+        } else {
+            position.min(duration)
+        }
+    }
+
+    /// Returns the index of the frame at the current playback position.
+    ///
+    /// Returns [`None`] if the recording is empty.
+    #[inline]
+    pub fn frame_index(&self) -> Option<usize> {
+        self.index_at(self.position())
+    }
+
+    /// Returns the time until the frame following the current one is reached.
+    ///
+    /// Returns [`None`] if paused, at the end of a recording without looping
+    /// or if there is no other frame to loop to.
+    pub fn time_to_next_frame(&self) -> Option<Duration> {
+        if self.is_paused() || (self.looping && self.is_single_timestamp()) {
+            return None;
+        }
+        let position = self.position();
+        let next = match self.frames.get(self.index_at(position)? + 1) {
+            Some(next) => next.timestamp,
+            None if self.looping => self.duration(),
+            None => return None,
+        };
+        Some(next.saturating_sub(position).div_f64(self.speed))
+    }
+
+    /// Returns whether the end of the recording was reached.
+    ///
+    /// Always `false` when looping.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.position() >= self.duration()
+    }
+
+    /// Returns the playback speed multiplier.
+    #[inline]
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Sets the playback speed multiplier.
+    ///
+    /// # Panics
+    /// Panics if the speed is negative, zero or not finite.
+    pub fn set_speed(&mut self, speed: f64) {
+        assert!(
+            speed.is_finite() && speed > 0.0,
+            "invalid playback speed {speed}"
+        );
+        self.reanchor();
+        self.speed = speed;
+    }
+
+    /// Returns whether playback wraps around at the end.
+    #[inline]
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Sets whether playback wraps around at the end.
+    #[inline]
+    pub fn set_looping(&mut self, looping: bool) {
+        self.reanchor();
+        self.looping = looping;
+    }
+
+    /// Returns whether playback is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.anchor.is_none()
+    }
+
+    /// Pauses playback at the current position.
+    #[inline]
+    pub fn pause(&mut self) {
+        self.position = self.position();
+        self.anchor = None;
+    }
+
+    /// Resumes playback from the current position.
+    #[inline]
+    pub fn resume(&mut self) {
+        if self.anchor.is_none() {
+            self.anchor = Some(Instant::now());
+        }
+    }
+
+    /// Moves the playback position to the given timestamp.
+    #[inline]
+    pub fn seek(&mut self, timestamp: Duration) {
+        self.position = timestamp;
+        if self.anchor.is_some() {
+            self.anchor = Some(Instant::now());
+        }
+    }
+
+    /// Writes the frame at the current playback position into the [`MumbleWriter`].
+    ///
+    /// Returns whether a new frame was written.
+    /// Needs to be called regularly, for example once per frame of the original recording.
+    #[cfg(any(windows, unix))]
+    pub fn update(&mut self) -> bool {
+        let index = self.frame_index();
+        if self.writer.is_none() || index.is_none() || index == self.written {
+            return false;
+        }
+        let mem = self.read();
+        if let Some(writer) = &mut self.writer {
+            writer.write(&mem);
+        }
+        self.written = index;
+        true
+    }
+
+    /// Returns whether all frames share the same timestamp.
+    #[inline]
+    fn is_single_timestamp(&self) -> bool {
+        self.frames.first().map(|frame| frame.timestamp)
+            == self.frames.last().map(|frame| frame.timestamp)
+    }
+
+    /// Moves the anchor to the current position.
+    fn reanchor(&mut self) {
+        self.position = self.position();
+        if self.anchor.is_some() {
+            self.anchor = Some(Instant::now());
+        }
+    }
+
+    /// Returns the index of the frame at the given timestamp.
+    #[inline]
+    fn index_at(&self, timestamp: Duration) -> Option<usize> {
+        if self.frames.is_empty() {
+            None
+        } else {
+            let after = self
+                .frames
+                .partition_point(|frame| frame.timestamp <= timestamp);
+            Some(after.saturating_sub(1))
+        }
+    }
+
+    /// Decodes the frame at the given index on top of the contents of the previous frame.
+    fn decode(&self, index: usize, mem: &mut LinkedMem) {
+        let mut reader = &self.data[self.frames[index].offset..];
+        read_frame(&mut reader, mem).expect("recording validated on creation");
+    }
+
+    /// Returns the contents of the frame at the given index.
+    ///
+    /// Decodes forward from the previously returned frame or the closest keyframe.
+    fn frame_at(&self, index: usize) -> LinkedMem {
+        let mut guard = self.cursor.lock().unwrap_or_else(PoisonError::into_inner);
+        let cursor = match guard.as_mut() {
+            Some(cursor) if cursor.index <= index && index - cursor.index <= KEYFRAME_INTERVAL => {
+                cursor
+            }
+            _ => {
+                let keyframe = index / KEYFRAME_INTERVAL;
+                let mut mem = self.keyframes[keyframe].clone();
+                self.decode(keyframe * KEYFRAME_INTERVAL, &mut mem);
+                guard.insert(Cursor {
+                    index: keyframe * KEYFRAME_INTERVAL,
+                    mem,
+                })
+            }
+        };
+        while cursor.index < index {
+            cursor.index += 1;
+            self.decode(cursor.index, &mut cursor.mem);
+        }
+        cursor.mem.clone()
+    }
+}
+
+impl LinkSource for Replayer {
+    /// Reads the frame at the current playback position.
+    ///
+    /// Returns empty contents if the recording has no frames.
+    #[inline]
+    fn read(&self) -> LinkedMem {
+        match self.frame_index() {
+            Some(index) => self.frame_at(index),
+            None => LinkedMem::default(),
+        }
+    }
+}
+
+impl fmt::Debug for Replayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replayer")
+            .field("header", &self.header)
+            .field("frames", &self.frames.len())
+            .field("position", &self.position())
+            .field("speed", &self.speed)
+            .field("looping", &self.looping)
+            .field("paused", &self.is_paused())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{recording::Recorder, MumblePtr};
+
+    const FRAMES: usize = 3 * KEYFRAME_INTERVAL + 50;
+
+    fn frame(index: usize) -> LinkedMem {
+        let mut mem = LinkedMem {
+            ui_version: 2,
+            ui_tick: index as u32,
+            ..LinkedMem::default()
+        };
+        mem.avatar.position = [index as f32, 0.0, 0.0];
+        mem.context.map_id = (index / 100) as u32;
+        let identity = format!(r#"{{"n":{}}}"#, index / 30);
+        for (unit, char) in mem.identity.iter_mut().zip(identity.encode_utf16()) {
+            *unit = char;
+        }
+        mem
+    }
+
+    fn timestamp(index: usize) -> Duration {
+        Duration::from_millis(10 * index as u64)
+    }
+
+    fn record(timestamps: &[Duration]) -> Replayer {
+        let mut link = LinkedMem::default();
+        let ptr = unsafe { MumblePtr::new(&mut link) }.unwrap();
+        let mut recorder = Recorder::new(ptr, Vec::new()).unwrap();
+        for (index, timestamp) in timestamps.iter().enumerate() {
+            recorder.write_frame(frame(index), *timestamp).unwrap();
+        }
+        Replayer::new(recorder.into_inner()).unwrap()
+    }
+
+    fn replayer() -> Replayer {
+        let timestamps: Vec<_> = (0..FRAMES).map(timestamp).collect();
+        let mut replayer = record(&timestamps);
+        replayer.pause();
+        replayer
+    }
+
+    #[track_caller]
+    fn assert_frame(replayer: &Replayer, index: usize) {
+        assert_eq!(replayer.frame_index(), Some(index));
+        assert_eq!(
+            format!("{:?}", replayer.read()),
+            format!("{:?}", frame(index)),
+            "frame {index}"
+        );
+    }
+
+    #[test]
+    fn seek() {
+        let mut replayer = replayer();
+        assert_eq!(replayer.frame_count(), FRAMES);
+        assert_eq!(replayer.duration(), timestamp(FRAMES - 1));
+        assert_frame(&replayer, 0);
+
+        let seeks = [
+            10,
+            20,
+            KEYFRAME_INTERVAL - 1,
+            KEYFRAME_INTERVAL,
+            KEYFRAME_INTERVAL + 1,
+            3 * KEYFRAME_INTERVAL + 10,
+            KEYFRAME_INTERVAL + 5,
+            KEYFRAME_INTERVAL - 5,
+            2 * KEYFRAME_INTERVAL + 100,
+            2 * KEYFRAME_INTERVAL + 99,
+            0,
+            FRAMES - 1,
+        ];
+        for index in seeks {
+            replayer.seek(timestamp(index));
+            assert_frame(&replayer, index);
+        }
+
+        replayer.seek(timestamp(120) + Duration::from_millis(5));
+        assert_frame(&replayer, 120);
+    }
+
+    #[test]
+    fn end() {
+        let mut replayer = replayer();
+        replayer.seek(timestamp(FRAMES + 100));
+        assert_eq!(replayer.position(), replayer.duration());
+        assert!(replayer.is_finished());
+        assert_frame(&replayer, FRAMES - 1);
+    }
+
+    #[test]
+    fn looping() {
+        let mut replayer = replayer();
+        replayer.set_looping(true);
+
+        replayer.seek(replayer.duration() + timestamp(15));
+        assert!(!replayer.is_finished());
+        assert_eq!(replayer.position(), timestamp(15));
+        assert_frame(&replayer, 15);
+
+        replayer.seek(timestamp(FRAMES - 2));
+        assert_frame(&replayer, FRAMES - 2);
+        replayer.seek(2 * replayer.duration() + timestamp(KEYFRAME_INTERVAL + 3));
+        assert_frame(&replayer, KEYFRAME_INTERVAL + 3);
+    }
+
+    #[test]
+    fn looping_single_frame() {
+        let mut replayer = record(&[Duration::ZERO]);
+        replayer.set_looping(true);
+        assert_eq!(replayer.time_to_next_frame(), None);
+        assert_frame(&replayer, 0);
+    }
+
+    #[test]
+    fn looping_equal_timestamps() {
+        let mut replayer = record(&[Duration::ZERO; 3]);
+        replayer.set_looping(true);
+        assert_eq!(replayer.time_to_next_frame(), None);
+        assert_frame(&replayer, 2);
+
+        let mut replayer = record(&[timestamp(3); 3]);
+        replayer.set_looping(true);
+        assert_eq!(replayer.time_to_next_frame(), None);
+    }
+
+    #[test]
+    fn empty() {
+        let replayer = record(&[]);
+        assert_eq!(replayer.frame_count(), 0);
+        assert_eq!(replayer.frame_index(), None);
+        assert_eq!(replayer.time_to_next_frame(), None);
+        assert_eq!(replayer.read().ui_tick, 0);
+    }
+}
@@ -0,0 +1,271 @@
+//! Serde helpers for the raw MumbleLink buffers.
+//!
+//! Human-readable formats use decoded representations, other formats use the raw values.
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+/// Wide char buffers as decoded strings.
+///
+/// Buffers with invalid UTF-16 or data after the first `0` fall back to all of their code units.
+pub mod wide {
+    use super::*;
+    use crate::{decode_wide, until_nul};
+
+    pub fn serialize<S, const N: usize>(wide: &[u16; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let len = until_nul(wide).len();
+            if wide[len..].iter().all(|unit| *unit == 0) {
+                if let Ok(string) = decode_wide(wide) {
+                    return serializer.serialize_str(&string);
+                }
+            }
+        }
+        wide.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u16; N], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(WideVisitor)
+        } else {
+            deserializer.deserialize_seq(WideVisitor)
+        }
+    }
+
+    struct WideVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for WideVisitor<N> {
+        type Value = [u16; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a string or sequence of at most {N} utf-16 code units")
+        }
+
+        fn visit_str<E>(self, string: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let mut buffer = [0; N];
+            for (i, unit) in string.encode_utf16().enumerate() {
+                *buffer
+                    .get_mut(i)
+                    .ok_or_else(|| E::invalid_length(string.encode_utf16().count(), &self))? = unit;
+            }
+            Ok(buffer)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut buffer = [0; N];
+            let mut len = 0;
+            while let Some(unit) = seq.next_element()? {
+                *buffer
+                    .get_mut(len)
+                    .ok_or_else(|| de::Error::invalid_length(len + 1, &self))? = unit;
+                len += 1;
+            }
+            Ok(buffer)
+        }
+    }
+}
+
+/// Raw `sockaddr` buffers as [`SocketAddr`](std::net::SocketAddr) strings.
+///
+/// Empty buffers are `null`, buffers not representable as address fall back to their bytes.
+pub mod sockaddr {
+    use super::*;
+    use crate::{decode_sockaddr, encode_sockaddr};
+    use std::net::SocketAddr;
+
+    pub fn serialize<S>(bytes: &[u8; 28], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return bytes.serialize(serializer);
+        }
+        if bytes.iter().all(|byte| *byte == 0) {
+            return serializer.serialize_none();
+        }
+        match decode_sockaddr(bytes) {
+            Some(addr) if encode_sockaddr(&addr) == *bytes => serializer.serialize_some(&addr),
+            _ => bytes.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 28], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(SockaddrVisitor)
+        } else {
+            <[u8; 28]>::deserialize(deserializer)
+        }
+    }
+
+    struct SockaddrVisitor;
+
+    impl<'de> Visitor<'de> for SockaddrVisitor {
+        type Value = [u8; 28];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a socket address, null or 28 bytes")
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok([0; 28])
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok([0; 28])
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_str<E>(self, string: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let addr: SocketAddr = string.parse().map_err(E::custom)?;
+            Ok(encode_sockaddr(&addr))
+        }
+
+        fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            bytes
+                .try_into()
+                .map_err(|_| E::invalid_length(bytes.len(), &self))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut bytes = [0; 28];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<u8>()?.is_some() {
+                return Err(de::Error::invalid_length(29, &self));
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+/// Raw UI state bits as [`UiState`](crate::UiState).
+pub mod ui_state {
+    use super::*;
+    use crate::UiState;
+
+    #[inline]
+    pub fn serialize<S>(bits: &u32, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        UiState::from_bits_retain(*bits).serialize(serializer)
+    }
+
+    #[inline]
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u32, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UiState::deserialize(deserializer).map(|state| state.bits())
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use crate::{LinkedMem, UiState};
+    use std::net::SocketAddr;
+
+    fn wide<const N: usize>(string: &str) -> [u16; N] {
+        let mut buffer = [0; N];
+        for (unit, char) in buffer.iter_mut().zip(string.encode_utf16()) {
+            *unit = char;
+        }
+        buffer
+    }
+
+    fn roundtrip(mem: &LinkedMem) -> (serde_json::Value, LinkedMem) {
+        let json = serde_json::to_value(mem).unwrap();
+        let read = serde_json::from_value(json.clone()).unwrap();
+        (json, read)
+    }
+
+    #[test]
+    fn linked_mem() {
+        let mut mem = LinkedMem {
+            ui_version: 2,
+            ui_tick: 1234,
+            name: wide("Guild Wars 2"),
+            identity: wide(r#"{"name":"Hä😀"}"#),
+            context_len: 48,
+            ..LinkedMem::default()
+        };
+        mem.avatar.position = [1.5, -2.0, 3.25];
+        mem.context.map_id = 15;
+        mem.context.ui_state = (UiState::IS_MAP_OPEN | UiState::GAME_HAS_FOCUS).bits() | 0x100;
+        mem.context
+            .set_server_socket_addr(&"127.0.0.1:6112".parse::<SocketAddr>().unwrap());
+
+        let (json, read) = roundtrip(&mem);
+        assert_eq!(json["name"], "Guild Wars 2");
+        assert_eq!(json["identity"], r#"{"name":"Hä😀"}"#);
+        assert_eq!(json["description"], "");
+        assert_eq!(json["context"]["server_address"], "127.0.0.1:6112");
+        assert_eq!(
+            json["context"]["ui_state"],
+            serde_json::json!(["IS_MAP_OPEN", "GAME_HAS_FOCUS", "0x100"])
+        );
+        assert_eq!(format!("{read:?}"), format!("{mem:?}"));
+    }
+
+    #[test]
+    fn wide_fallback() {
+        let mut mem = LinkedMem::default();
+        mem.name[..3].copy_from_slice(&[0x61, 0xd800, 0x62]);
+        mem.identity[..3].copy_from_slice(&[0x61, 0, 0x62]);
+
+        let (json, read) = roundtrip(&mem);
+        assert_eq!(json["name"].as_array().unwrap().len(), 256);
+        assert_eq!(json["name"][1], 0xd800);
+        assert_eq!(json["identity"][2], 0x62);
+        assert_eq!(format!("{read:?}"), format!("{mem:?}"));
+    }
+
+    #[test]
+    fn wide_too_long() {
+        let mut json = serde_json::to_value(LinkedMem::default()).unwrap();
+        json["name"] = "x".repeat(257).into();
+        assert!(serde_json::from_value::<LinkedMem>(json).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::LinkedMem;
+use std::ops::Deref;
+
+/// A copy of [`LinkedMem`] contents read via [`MumblePtr::read_consistent`](crate::MumblePtr::read_consistent).
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Copied [`LinkedMem`] contents.
+    pub mem: LinkedMem,
+
+    /// Whether the contents were confirmed to originate from a single frame.
+    ///
+    /// `false` if the `ui_tick` kept changing for all attempts.
+    pub consistent: bool,
+
+    /// Number of attempts needed for the read.
+    pub attempts: u32,
+}
+
+impl Snapshot {
+    /// Returns whether the contents were confirmed to originate from a single frame.
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    /// Returns the copied [`LinkedMem`] contents.
+    #[inline]
+    pub fn into_inner(self) -> LinkedMem {
+        self.mem
+    }
+}
+
+impl Deref for Snapshot {
+    type Target = LinkedMem;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.mem
+    }
+}
+
+impl From<Snapshot> for LinkedMem {
+    #[inline]
+    fn from(snapshot: Snapshot) -> Self {
+        snapshot.mem
+    }
+}
@@ -0,0 +1,85 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+/// Windows `AF_INET` address family.
+const AF_INET: u16 = 2;
+
+/// Windows `AF_INET6` address family.
+const AF_INET6: u16 = 23;
+
+/// Decodes a Windows `sockaddr_in` or `sockaddr_in6` as written by the game.
+///
+/// Returns [`None`] for unknown address families, for example if no server address is present.
+/// Does not depend on the platform socket headers.
+pub fn decode_sockaddr(bytes: &[u8; 28]) -> Option<SocketAddr> {
+    let family = u16::from_le_bytes([bytes[0], bytes[1]]);
+    let port = u16::from_be_bytes([bytes[2], bytes[3]]);
+    match family {
+        AF_INET => {
+            let ip = Ipv4Addr::new(bytes[4], bytes[5], bytes[6], bytes[7]);
+            Some(SocketAddrV4::new(ip, port).into())
+        }
+        AF_INET6 => {
+            // std keeps flowinfo and scope id as stored in memory without byte order conversion
+            let flowinfo = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+            let ip: [u8; 16] = bytes[8..24].try_into().unwrap();
+            let scope_id = u32::from_le_bytes(bytes[24..28].try_into().unwrap());
+            Some(SocketAddrV6::new(Ipv6Addr::from(ip), port, flowinfo, scope_id).into())
+        }
+        _ => None,
+    }
+}
+
+/// Encodes a [`SocketAddr`] as Windows `sockaddr_in` or `sockaddr_in6` like the game does.
+pub fn encode_sockaddr(addr: &SocketAddr) -> [u8; 28] {
+    let mut bytes = [0; 28];
+    bytes[2..4].copy_from_slice(&addr.port().to_be_bytes());
+    match addr {
+        SocketAddr::V4(addr) => {
+            bytes[0..2].copy_from_slice(&AF_INET.to_le_bytes());
+            bytes[4..8].copy_from_slice(&addr.ip().octets());
+        }
+        SocketAddr::V6(addr) => {
+            bytes[0..2].copy_from_slice(&AF_INET6.to_le_bytes());
+            bytes[4..8].copy_from_slice(&addr.flowinfo().to_le_bytes());
+            bytes[8..24].copy_from_slice(&addr.ip().octets());
+            bytes[24..28].copy_from_slice(&addr.scope_id().to_le_bytes());
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv4() {
+        let mut bytes = [0; 28];
+        bytes[..8].copy_from_slice(&[0x02, 0x00, 0x17, 0xe0, 192, 168, 1, 42]);
+        let addr: SocketAddr = "192.168.1.42:6112".parse().unwrap();
+        assert_eq!(decode_sockaddr(&bytes), Some(addr));
+        assert_eq!(encode_sockaddr(&addr), bytes);
+    }
+
+    #[test]
+    fn ipv6() {
+        let mut bytes = [0; 28];
+        bytes[..8].copy_from_slice(&[0x17, 0x00, 0x01, 0xbb, 0x78, 0x56, 0x34, 0x12]);
+        bytes[8..24].copy_from_slice(&[
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01,
+        ]);
+        bytes[24..].copy_from_slice(&[0x05, 0x00, 0x00, 0x00]);
+        let addr = SocketAddrV6::new("2001:db8::1".parse().unwrap(), 443, 0x12345678, 5).into();
+        assert_eq!(decode_sockaddr(&bytes), Some(addr));
+        assert_eq!(encode_sockaddr(&addr), bytes);
+    }
+
+    #[test]
+    fn unknown_family() {
+        assert_eq!(decode_sockaddr(&[0; 28]), None);
+
+        let mut bytes = [0; 28];
+        bytes[..8].copy_from_slice(&[0x0a, 0x00, 0x17, 0xe0, 127, 0, 0, 1]);
+        assert_eq!(decode_sockaddr(&bytes), None);
+    }
+}
@@ -0,0 +1,393 @@
+use crate::{
+    decode_sockaddr, decode_wide, decode_wide_lossy, until_nul, Context, DecodeError, Identity,
+    IdentityError, LinkedMem, MapType, Mount, MumblePtr, Position, RawContext, UiState,
+};
+use std::net::SocketAddr;
+
+/// A source of [`LinkedMem`] data.
+///
+/// Implemented by [`MumblePtr`] for live shared memory and by [`LinkedMem`] for owned snapshots.
+/// Custom sources like recordings or test fixtures only need to implement [`LinkSource::read`].
+/// All other reads default to reading the entire [`LinkedMem`] and can be overridden for efficiency.
+pub trait LinkSource {
+    /// Reads the entire current [`LinkedMem`] contents.
+    fn read(&self) -> LinkedMem;
+
+    /// Reads the current `ui_version`.
+    #[inline]
+    fn read_ui_version(&self) -> u32 {
+        self.read().ui_version
+    }
+
+    /// Reads the current `ui_tick`.
+    #[inline]
+    fn read_ui_tick(&self) -> u32 {
+        self.read().ui_tick
+    }
+
+    /// Reads the current player avatar [`Position`].
+    #[inline]
+    fn read_avatar(&self) -> Position {
+        self.read().avatar
+    }
+
+    /// Reads the current game name.
+    #[inline]
+    fn read_name(&self) -> Vec<u16> {
+        until_nul(&self.read().name).to_vec()
+    }
+
+    /// Reads the current game name as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    fn read_name_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_name())
+    }
+
+    /// Reads the current game name as [`String`], replacing invalid UTF-16.
+    #[inline]
+    fn read_name_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_name())
+    }
+
+    /// Reads the current camera [`Position`].
+    #[inline]
+    fn read_camera(&self) -> Position {
+        self.read().camera
+    }
+
+    /// Reads the current player identity.
+    #[inline]
+    fn read_identity(&self) -> Vec<u16> {
+        until_nul(&self.read().identity).to_vec()
+    }
+
+    /// Reads the current player identity as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    fn read_identity_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_identity())
+    }
+
+    /// Reads the current player identity as [`String`], replacing invalid UTF-16.
+    #[inline]
+    fn read_identity_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_identity())
+    }
+
+    /// Parses the current player identity JSON contents without requiring serde.
+    ///
+    /// See [`Identity::from_wide`].
+    #[inline]
+    fn decode_identity(&self) -> Result<Identity, IdentityError> {
+        Identity::from_wide(&self.read_identity())
+    }
+
+    /// Parses the current player identity JSON contents.
+    #[cfg(feature = "json")]
+    fn parse_identity(&self) -> serde_json::Result<Identity> {
+        serde_json::from_str(&self.read_identity_string_lossy())
+    }
+
+    /// Reads the current [`Context`].
+    #[inline]
+    fn read_context(&self) -> Context {
+        self.read_raw_context().into()
+    }
+
+    /// Reads the current [`RawContext`].
+    #[inline]
+    fn read_raw_context(&self) -> RawContext {
+        self.read().context
+    }
+
+    /// Reads the current server address.
+    #[inline]
+    fn read_server_address(&self) -> [u8; 28] {
+        self.read_context().server_address
+    }
+
+    /// Reads the current server address as [`SocketAddr`].
+    #[inline]
+    fn read_server_socket_addr(&self) -> Option<SocketAddr> {
+        decode_sockaddr(&self.read_server_address())
+    }
+
+    /// Reads the current map id.
+    #[inline]
+    fn read_map_id(&self) -> u32 {
+        self.read_context().map_id
+    }
+
+    /// Reads the current map type.
+    #[inline]
+    fn read_map_type(&self) -> u32 {
+        self.read_raw_context().map_type
+    }
+
+    /// Reads the current map type as [`MapType`].
+    #[inline]
+    fn read_map_type_typed(&self) -> MapType {
+        self.read_map_type().into()
+    }
+
+    /// Reads the current shard id.
+    #[inline]
+    fn read_shard_id(&self) -> u32 {
+        self.read_context().shard_id
+    }
+
+    /// Reads the current instance id.
+    #[inline]
+    fn read_instance(&self) -> u32 {
+        self.read_context().instance
+    }
+
+    /// Reads the current build id.
+    #[inline]
+    fn read_build_id(&self) -> u32 {
+        self.read_context().build_id
+    }
+
+    /// Reads the current UI state.
+    #[inline]
+    fn read_ui_state(&self) -> UiState {
+        self.read_context().ui_state
+    }
+
+    /// Reads the current compass width in pixels.
+    #[inline]
+    fn read_compass_width(&self) -> u16 {
+        self.read_context().compass_width
+    }
+
+    /// Reads the current compass height in pixels.
+    #[inline]
+    fn read_compass_height(&self) -> u16 {
+        self.read_context().compass_height
+    }
+
+    /// Reads the current compass dimensions in pixels.
+    #[inline]
+    fn read_compass_dimensions(&self) -> [u16; 2] {
+        [self.read_compass_width(), self.read_compass_height()]
+    }
+
+    /// Reads the current compass rotation in radians.
+    #[inline]
+    fn read_compass_rotation(&self) -> f32 {
+        self.read_context().compass_rotation
+    }
+
+    /// Reads the current player position x in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_player_x(&self) -> f32 {
+        self.read_context().player_x
+    }
+
+    /// Reads the current player position y in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_player_y(&self) -> f32 {
+        self.read_context().player_y
+    }
+
+    /// Reads the current player position in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_player_position(&self) -> [f32; 2] {
+        [self.read_player_x(), self.read_player_y()]
+    }
+
+    /// Reads the current map center x in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_map_center_x(&self) -> f32 {
+        self.read_context().map_center_x
+    }
+
+    /// Reads the current map center y in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_map_center_y(&self) -> f32 {
+        self.read_context().map_center_y
+    }
+
+    /// Reads the current map center in continent coordinates.
+    ///
+    /// Not updated in competitive modes.
+    #[inline]
+    fn read_map_center(&self) -> [f32; 2] {
+        [self.read_map_center_x(), self.read_map_center_y()]
+    }
+
+    /// Reads the map scale.
+    #[inline]
+    fn read_map_scale(&self) -> f32 {
+        self.read_context().map_scale
+    }
+
+    /// Reads the process id.
+    #[inline]
+    fn read_process_id(&self) -> u32 {
+        self.read_context().process_id
+    }
+
+    /// Reads the currently used mount.
+    #[inline]
+    fn read_mount_index(&self) -> Mount {
+        self.read_context().mount_index
+    }
+
+    /// Reads the game description.
+    #[inline]
+    fn read_description(&self) -> Vec<u16> {
+        until_nul(&self.read().description).to_vec()
+    }
+
+    /// Reads the game description as [`String`].
+    ///
+    /// Fails on invalid UTF-16.
+    #[inline]
+    fn read_description_string(&self) -> Result<String, DecodeError> {
+        decode_wide(&self.read_description())
+    }
+
+    /// Reads the game description as [`String`], replacing invalid UTF-16.
+    #[inline]
+    fn read_description_string_lossy(&self) -> String {
+        decode_wide_lossy(&self.read_description())
+    }
+}
+
+/// Forwards the overridable [`LinkSource`] reads to another implementation.
+macro_rules! forward_link_source {
+    ( $self:ident => $target:expr ) => {
+        forward_link_source! {
+            $self => $target;
+            read -> LinkedMem,
+            read_ui_version -> u32,
+            read_ui_tick -> u32,
+            read_avatar -> Position,
+            read_name -> Vec<u16>,
+            read_camera -> Position,
+            read_identity -> Vec<u16>,
+            read_context -> Context,
+            read_raw_context -> RawContext,
+            read_server_address -> [u8; 28],
+            read_map_id -> u32,
+            read_map_type -> u32,
+            read_shard_id -> u32,
+            read_instance -> u32,
+            read_build_id -> u32,
+            read_ui_state -> UiState,
+            read_compass_width -> u16,
+            read_compass_height -> u16,
+            read_compass_rotation -> f32,
+            read_player_x -> f32,
+            read_player_y -> f32,
+            read_map_center_x -> f32,
+            read_map_center_y -> f32,
+            read_map_scale -> f32,
+            read_process_id -> u32,
+            read_mount_index -> Mount,
+            read_description -> Vec<u16>,
+        }
+    };
+    ( $self:ident => $target:expr; $( $name:ident -> $ret:ty ),* $(,)? ) => {
+        $(
+            #[inline]
+            fn $name(&$self) -> $ret {
+                $target.$name()
+            }
+        )*
+    };
+}
+
+impl LinkSource for MumblePtr {
+    forward_link_source!(self => (*self));
+}
+
+impl LinkSource for LinkedMem {
+    #[inline]
+    fn read(&self) -> LinkedMem {
+        self.clone()
+    }
+
+    #[inline]
+    fn read_ui_version(&self) -> u32 {
+        self.ui_version
+    }
+
+    #[inline]
+    fn read_ui_tick(&self) -> u32 {
+        self.ui_tick
+    }
+
+    #[inline]
+    fn read_avatar(&self) -> Position {
+        self.avatar.clone()
+    }
+
+    #[inline]
+    fn read_name(&self) -> Vec<u16> {
+        until_nul(&self.name).to_vec()
+    }
+
+    #[inline]
+    fn read_camera(&self) -> Position {
+        self.camera.clone()
+    }
+
+    #[inline]
+    fn read_identity(&self) -> Vec<u16> {
+        until_nul(&self.identity).to_vec()
+    }
+
+    #[inline]
+    fn decode_identity(&self) -> Result<Identity, IdentityError> {
+        LinkedMem::decode_identity(self)
+    }
+
+    #[inline]
+    fn read_context(&self) -> Context {
+        self.typed_context()
+    }
+
+    #[inline]
+    fn read_raw_context(&self) -> RawContext {
+        self.context.clone()
+    }
+
+    #[inline]
+    fn read_description(&self) -> Vec<u16> {
+        until_nul(&self.description).to_vec()
+    }
+}
+
+#[cfg(any(windows, unix))]
+impl LinkSource for crate::MumbleLink {
+    forward_link_source!(self => self.as_mumble_ptr());
+}
+
+impl<T> LinkSource for &T
+where
+    T: LinkSource + ?Sized,
+{
+    forward_link_source!(self => (**self));
+}
+
+impl<T> LinkSource for Box<T>
+where
+    T: LinkSource + ?Sized,
+{
+    forward_link_source!(self => (**self));
+}
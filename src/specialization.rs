@@ -0,0 +1,262 @@
+use crate::{
+    util::{display_unknown, serde_primitive},
+    Identity, Profession,
+};
+
+/// Expansion introducing an elite specialization.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum::Display, strum::AsRefStr,
+)]
+pub enum Expansion {
+    #[strum(serialize = "Heart of Thorns")]
+    HeartOfThorns,
+
+    #[strum(serialize = "Path of Fire")]
+    PathOfFire,
+
+    #[strum(serialize = "End of Dragons")]
+    EndOfDragons,
+
+    #[strum(serialize = "Visions of Eternity")]
+    VisionsOfEternity,
+}
+
+macro_rules! specializations {
+    ( $( $( #[$meta:meta] )* $name:ident = $id:literal => $profession:ident $( , $expansion:ident )? ; )* ) => {
+        /// Core or elite specialization.
+        ///
+        /// See [API:2/specializations](https://wiki.guildwars2.com/wiki/API:2/specializations) for details.
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Hash,
+            strum::AsRefStr,
+            strum::IntoStaticStr,
+            num_enum::FromPrimitive,
+            num_enum::IntoPrimitive,
+        )]
+        #[repr(u32)]
+        pub enum Specialization {
+            $(
+                $( #[$meta] )*
+                $name = $id,
+            )*
+
+            /// Specialization unknown to this version of the crate.
+            #[num_enum(catch_all)]
+            Unknown(u32),
+        }
+
+        impl Specialization {
+            /// Returns the [`Profession`] owning the specialization.
+            pub fn profession(&self) -> Option<Profession> {
+                match self {
+                    $( Self::$name => Some(Profession::$profession), )*
+                    Self::Unknown(_) => None,
+                }
+            }
+
+            /// Returns the [`Expansion`] introducing the specialization.
+            ///
+            /// [`None`] for core specializations.
+            pub fn expansion(&self) -> Option<Expansion> {
+                match self {
+                    $( Self::$name => specializations!(@expansion $( $expansion )?), )*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+    };
+    (@expansion $expansion:ident) => { Some(Expansion::$expansion) };
+    (@expansion) => { None };
+}
+
+specializations! {
+    Dueling = 1 => Mesmer;
+    #[strum(serialize = "Death Magic")] DeathMagic = 2 => Necromancer;
+    Invocation = 3 => Revenant;
+    Strength = 4 => Warrior;
+    Druid = 5 => Ranger, HeartOfThorns;
+    Explosives = 6 => Engineer;
+    Daredevil = 7 => Thief, HeartOfThorns;
+    Marksmanship = 8 => Ranger;
+    Retribution = 9 => Revenant;
+    Domination = 10 => Mesmer;
+    Tactics = 11 => Warrior;
+    Salvation = 12 => Revenant;
+    Valor = 13 => Guardian;
+    Corruption = 14 => Revenant;
+    Devastation = 15 => Revenant;
+    Radiance = 16 => Guardian;
+    Water = 17 => Elementalist;
+    Berserker = 18 => Warrior, HeartOfThorns;
+    #[strum(serialize = "Blood Magic")] BloodMagic = 19 => Necromancer;
+    #[strum(serialize = "Shadow Arts")] ShadowArts = 20 => Thief;
+    Tools = 21 => Engineer;
+    Defense = 22 => Warrior;
+    Inspiration = 23 => Mesmer;
+    Illusions = 24 => Mesmer;
+    #[strum(serialize = "Nature Magic")] NatureMagic = 25 => Ranger;
+    Earth = 26 => Elementalist;
+    Dragonhunter = 27 => Guardian, HeartOfThorns;
+    #[strum(serialize = "Deadly Arts")] DeadlyArts = 28 => Thief;
+    Alchemy = 29 => Engineer;
+    Skirmishing = 30 => Ranger;
+    Fire = 31 => Elementalist;
+    Beastmastery = 32 => Ranger;
+    #[strum(serialize = "Wilderness Survival")] WildernessSurvival = 33 => Ranger;
+    Reaper = 34 => Necromancer, HeartOfThorns;
+    #[strum(serialize = "Critical Strikes")] CriticalStrikes = 35 => Thief;
+    Arms = 36 => Warrior;
+    Arcane = 37 => Elementalist;
+    Firearms = 38 => Engineer;
+    Curses = 39 => Necromancer;
+    Chronomancer = 40 => Mesmer, HeartOfThorns;
+    Air = 41 => Elementalist;
+    Zeal = 42 => Guardian;
+    Scrapper = 43 => Engineer, HeartOfThorns;
+    Trickery = 44 => Thief;
+    Chaos = 45 => Mesmer;
+    Virtues = 46 => Guardian;
+    Inventions = 47 => Engineer;
+    Tempest = 48 => Elementalist, HeartOfThorns;
+    Honor = 49 => Guardian;
+    #[strum(serialize = "Soul Reaping")] SoulReaping = 50 => Necromancer;
+    Discipline = 51 => Warrior;
+    Herald = 52 => Revenant, HeartOfThorns;
+    Spite = 53 => Necromancer;
+    Acrobatics = 54 => Thief;
+    Soulbeast = 55 => Ranger, PathOfFire;
+    Weaver = 56 => Elementalist, PathOfFire;
+    Holosmith = 57 => Engineer, PathOfFire;
+    Deadeye = 58 => Thief, PathOfFire;
+    Mirage = 59 => Mesmer, PathOfFire;
+    Scourge = 60 => Necromancer, PathOfFire;
+    Spellbreaker = 61 => Warrior, PathOfFire;
+    Firebrand = 62 => Guardian, PathOfFire;
+    Renegade = 63 => Revenant, PathOfFire;
+    Harbinger = 64 => Necromancer, EndOfDragons;
+    Willbender = 65 => Guardian, EndOfDragons;
+    Virtuoso = 66 => Mesmer, EndOfDragons;
+    Catalyst = 67 => Elementalist, EndOfDragons;
+    Bladesworn = 68 => Warrior, EndOfDragons;
+    Vindicator = 69 => Revenant, EndOfDragons;
+    Mechanist = 70 => Engineer, EndOfDragons;
+    Specter = 71 => Thief, EndOfDragons;
+    Untamed = 72 => Ranger, EndOfDragons;
+    Troubadour = 73 => Mesmer, VisionsOfEternity;
+    Paragon = 74 => Warrior, VisionsOfEternity;
+    Amalgam = 75 => Engineer, VisionsOfEternity;
+    Ritualist = 76 => Necromancer, VisionsOfEternity;
+    Antiquary = 77 => Thief, VisionsOfEternity;
+    Galeshot = 78 => Ranger, VisionsOfEternity;
+    Conduit = 79 => Revenant, VisionsOfEternity;
+    Evoker = 80 => Elementalist, VisionsOfEternity;
+    Luminary = 81 => Guardian, VisionsOfEternity;
+}
+
+impl Specialization {
+    /// Returns the name of the specialization.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.into()
+    }
+
+    /// Whether the specialization is an elite specialization.
+    #[inline]
+    pub fn is_elite(&self) -> bool {
+        self.expansion().is_some()
+    }
+}
+
+serde_primitive!(Specialization => u32);
+display_unknown!(Specialization);
+
+impl Identity {
+    /// Returns the equipped 3rd [`Specialization`].
+    ///
+    /// [`None`] if no specialization is present.
+    #[inline]
+    pub fn specialization(&self) -> Option<Specialization> {
+        (self.spec != 0).then(|| self.spec.into())
+    }
+
+    /// Returns the equipped elite [`Specialization`].
+    #[inline]
+    pub fn elite_spec(&self) -> Option<Specialization> {
+        self.specialization().filter(Specialization::is_elite)
+    }
+
+    /// Returns the display name of the profession.
+    ///
+    /// This is the elite specialization name if one is equipped, for example `"Vindicator"`, otherwise the profession name like `"Revenant"`.
+    /// Falls back to the profession name if the elite specialization belongs to another profession.
+    #[inline]
+    pub fn display_profession(&self) -> &'static str {
+        match self.elite_spec() {
+            Some(spec) if spec.profession() == Some(self.profession) => spec.name(),
+            _ => self.profession.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(profession: Profession, spec: u32) -> Identity {
+        let json = format!(
+            r#"{{"name":"Test Character","profession":{},"spec":{spec},"race":4,"map_id":1206,"world_id":2003,"team_color_id":0,"commander":false,"fov":0.873,"uisz":1}}"#,
+            u8::from(profession)
+        );
+        let wide: Vec<u16> = json.encode_utf16().collect();
+        Identity::from_wide(&wide).unwrap()
+    }
+
+    #[test]
+    fn catalogue() {
+        let spec = Specialization::from(69);
+        assert_eq!(spec, Specialization::Vindicator);
+        assert_eq!(spec.name(), "Vindicator");
+        assert_eq!(spec.profession(), Some(Profession::Revenant));
+        assert_eq!(spec.expansion(), Some(Expansion::EndOfDragons));
+        assert!(spec.is_elite());
+
+        let spec = Specialization::from(2);
+        assert_eq!(spec.name(), "Death Magic");
+        assert_eq!(spec.profession(), Some(Profession::Necromancer));
+        assert!(!spec.is_elite());
+
+        assert_eq!(Specialization::from(1000), Specialization::Unknown(1000));
+        assert_eq!(Specialization::Unknown(1000).profession(), None);
+    }
+
+    #[test]
+    fn display_profession() {
+        assert_eq!(
+            identity(Profession::Revenant, 69).display_profession(),
+            "Vindicator"
+        );
+        assert_eq!(
+            identity(Profession::Revenant, 3).display_profession(),
+            "Revenant"
+        );
+        assert_eq!(
+            identity(Profession::Revenant, 0).display_profession(),
+            "Revenant"
+        );
+        assert_eq!(
+            identity(Profession::Guardian, 69).display_profession(),
+            "Guardian"
+        );
+        assert_eq!(
+            identity(Profession::Guardian, 1000).display_profession(),
+            "Guardian"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{LinkSource, LinkedMem};
+use futures_core::Stream;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use tokio::{
+    task::JoinHandle,
+    time::{self, MissedTickBehavior},
+};
+
+/// Behavior when the consumer of a [`LinkStream`] falls behind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backpressure {
+    /// Only keep the most recent [`LinkedMem`].
+    Coalesce,
+
+    /// Buffer up to the given amount of [`LinkedMem`] reads and drop the oldest when full.
+    DropOldest(usize),
+}
+
+/// Options for a [`LinkStream`].
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Interval for polling the `ui_tick`.
+    pub interval: Duration,
+
+    /// Behavior when the consumer falls behind.
+    pub backpressure: Backpressure,
+}
+
+impl Default for StreamOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(20),
+            backpressure: Backpressure::Coalesce,
+        }
+    }
+}
+
+/// Asynchronous [`Stream`] of [`LinkedMem`] updates.
+///
+/// A background task polls the source and emits the contents whenever the `ui_tick` advances.
+/// The current contents are emitted first.
+/// Dropping the stream stops the background task.
+#[derive(Debug)]
+pub struct LinkStream {
+    shared: Arc<Mutex<Shared>>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    queue: VecDeque<LinkedMem>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+impl LinkStream {
+    /// Creates a new stream for the source with default options.
+    ///
+    /// # Panics
+    /// Panics when called outside of a Tokio runtime.
+    #[inline]
+    pub fn new<S>(source: S) -> Self
+    where
+        S: LinkSource + Send + 'static,
+    {
+        Self::with_options(source, StreamOptions::default())
+    }
+
+    /// Creates a new stream for the source with the given options.
+    ///
+    /// # Panics
+    /// Panics when called outside of a Tokio runtime.
+    pub fn with_options<S>(source: S, options: StreamOptions) -> Self
+    where
+        S: LinkSource + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let task = tokio::spawn(Self::run(source, options, shared.clone()));
+        Self { shared, task }
+    }
+
+    async fn run<S>(source: S, options: StreamOptions, shared: Arc<Mutex<Shared>>)
+    where
+        S: LinkSource,
+    {
+        let _guard = CloseGuard(shared.clone());
+        let mut interval = time::interval(options.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let mut last_tick = None;
+        loop {
+            interval.tick().await;
+
+            let tick = source.read_ui_tick();
+            if last_tick == Some(tick) {
+                continue;
+            }
+            last_tick = Some(tick);
+            let mem = source.read();
+
+            let mut shared = shared.lock().unwrap();
+            match options.backpressure {
+                Backpressure::Coalesce => shared.queue.clear(),
+                Backpressure::DropOldest(capacity) => {
+                    while shared.queue.len() >= capacity.max(1) {
+                        shared.queue.pop_front();
+                    }
+                }
+            }
+            shared.queue.push_back(mem);
+            if let Some(waker) = shared.waker.take() {
+                drop(shared);
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl Stream for LinkStream {
+    type Item = LinkedMem;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(mem) = shared.queue.pop_front() {
+            Poll::Ready(Some(mem))
+        } else if shared.closed {
+            Poll::Ready(None)
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for LinkStream {
+    #[inline]
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Marks the stream as closed when the background task ends.
+struct CloseGuard(Arc<Mutex<Shared>>);
+
+impl Drop for CloseGuard {
+    fn drop(&mut self) {
+        if let Ok(mut shared) = self.0.lock() {
+            shared.closed = true;
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
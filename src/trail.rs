@@ -0,0 +1,406 @@
+//! Trail recording in the `.trl` format used by TacO and Blish HUD.
+//!
+//! A `.trl` file starts with the format [`VERSION`] and the map id as 32-bit integers.
+//! It is followed by the trail points as `x`, `y` and `z` 32-bit floats in map meters.
+//! Segments are separated by a point at `(0, 0, 0)`.
+//! All values are little-endian.
+
+use crate::{
+    math::{length, sub},
+    LinkSource, Position, TrailError,
+};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Version of the `.trl` format.
+pub const VERSION: u32 = 0;
+
+/// Point separating segments.
+const SEPARATOR: [f32; 3] = [0.0; 3];
+
+/// Trail on a single map.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trail {
+    /// Map id of the trail.
+    pub map_id: u32,
+
+    /// Connected segments of points in map meters.
+    pub segments: Vec<Vec<[f32; 3]>>,
+}
+
+impl Trail {
+    /// Creates a new empty trail for the given map.
+    #[inline]
+    pub fn new(map_id: u32) -> Self {
+        Self {
+            map_id,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Returns whether the trail has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.iter().all(Vec::is_empty)
+    }
+
+    /// Returns an iterator over all points of the trail.
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = &[f32; 3]> {
+        self.segments.iter().flatten()
+    }
+
+    /// Reads a trail in `.trl` format.
+    pub fn read_from(mut reader: impl Read) -> Result<Self, TrailError> {
+        let version = u32::from_le_bytes(read_array(&mut reader)?);
+        if version != VERSION {
+            return Err(TrailError::UnsupportedVersion(version));
+        }
+        let map_id = u32::from_le_bytes(read_array(&mut reader)?);
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if data.len() % 12 != 0 {
+            return Err(TrailError::InvalidLength(data.len()));
+        }
+
+        let mut trail = Self::new(map_id);
+        let mut segment = Vec::new();
+        for chunk in data.chunks_exact(12) {
+            let point = [0, 4, 8].map(|i| {
+                f32::from_le_bytes(chunk[i..i + 4].try_into().expect("invalid chunk size"))
+            });
+            if point == SEPARATOR {
+                if !segment.is_empty() {
+                    trail.segments.push(segment);
+                    segment = Vec::new();
+                }
+            } else {
+                segment.push(point);
+            }
+        }
+        if !segment.is_empty() {
+            trail.segments.push(segment);
+        }
+        Ok(trail)
+    }
+
+    /// Writes the trail in `.trl` format.
+    ///
+    /// Empty segments are skipped.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&self.map_id.to_le_bytes())?;
+        let segments = self.segments.iter().filter(|segment| !segment.is_empty());
+        for (i, segment) in segments.enumerate() {
+            if i > 0 {
+                write_point(&mut writer, SEPARATOR)?;
+            }
+            for point in segment {
+                write_point(&mut writer, *point)?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Loads a trail from the `.trl` file at the given path.
+    #[inline]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TrailError> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Saves the trail to a `.trl` file at the given path.
+    #[inline]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Records [`Trail`]s from successive player avatar positions.
+///
+/// Points closer than the minimum distance to the previous point are dropped.
+/// A new segment is started when the map changes or the distance to the previous point exceeds the teleport distance.
+/// Returning to a previous map continues its trail with a new segment.
+#[derive(Debug, Clone)]
+pub struct TrailRecorder {
+    min_distance: f32,
+    teleport_distance: f32,
+    trails: Vec<Trail>,
+    current: Option<usize>,
+    last: Option<[f32; 3]>,
+}
+
+impl TrailRecorder {
+    /// Default minimum distance between points in meters.
+    pub const DEFAULT_MIN_DISTANCE: f32 = 1.0;
+
+    /// Default distance between points in meters considered a teleport.
+    pub const DEFAULT_TELEPORT_DISTANCE: f32 = 50.0;
+
+    /// Creates a new trail recorder with the default distances.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_distances(Self::DEFAULT_MIN_DISTANCE, Self::DEFAULT_TELEPORT_DISTANCE)
+    }
+
+    /// Creates a new trail recorder with the given minimum and teleport distances in meters.
+    #[inline]
+    pub fn with_distances(min_distance: f32, teleport_distance: f32) -> Self {
+        Self {
+            min_distance,
+            teleport_distance,
+            trails: Vec::new(),
+            current: None,
+            last: None,
+        }
+    }
+
+    /// Returns the recorded trails.
+    #[inline]
+    pub fn trails(&self) -> &[Trail] {
+        &self.trails
+    }
+
+    /// Returns the trail currently recorded to.
+    #[inline]
+    pub fn current(&self) -> Option<&Trail> {
+        self.current.map(|index| &self.trails[index])
+    }
+
+    /// Returns the recorded trails, consuming the recorder.
+    #[inline]
+    pub fn into_trails(self) -> Vec<Trail> {
+        self.trails
+    }
+
+    /// Ends the current segment.
+    ///
+    /// The next point starts a new segment.
+    #[inline]
+    pub fn split(&mut self) {
+        self.last = None;
+    }
+
+    /// Samples the player avatar position and map id from the source.
+    ///
+    /// Returns whether a point was recorded.
+    #[inline]
+    pub fn sample(&mut self, source: &impl LinkSource) -> bool {
+        let avatar = source.read_avatar();
+        let map_id = source.read_map_id();
+        self.push_avatar(map_id, &avatar)
+    }
+
+    /// Adds the given player avatar [`Position`] on the given map.
+    ///
+    /// Returns whether a point was recorded.
+    #[inline]
+    pub fn push_avatar(&mut self, map_id: u32, avatar: &Position) -> bool {
+        self.push(map_id, avatar.position)
+    }
+
+    /// Adds the given point on the given map.
+    ///
+    /// Points on map id `0`, at `(0, 0, 0)` or with non-finite coordinates are ignored.
+    /// Returns whether a point was recorded.
+    pub fn push(&mut self, map_id: u32, point: [f32; 3]) -> bool {
+        if map_id == 0 || point == SEPARATOR || point.iter().any(|value| !value.is_finite()) {
+            return false;
+        }
+
+        let index = match self.current {
+            Some(index) if self.trails[index].map_id == map_id => index,
+            _ => {
+                self.last = None;
+                let index = match self.trails.iter().position(|trail| trail.map_id == map_id) {
+                    Some(index) => index,
+                    None => {
+                        self.trails.push(Trail::new(map_id));
+                        self.trails.len() - 1
+                    }
+                };
+                self.current = Some(index);
+                index
+            }
+        };
+
+        let segments = &mut self.trails[index].segments;
+        match self.last {
+            Some(last) => {
+                let distance = length(sub(point, last));
+                if distance < self.min_distance {
+                    return false;
+                }
+                if distance > self.teleport_distance {
+                    segments.push(Vec::new());
+                }
+            }
+            None => segments.push(Vec::new()),
+        }
+        segments
+            .last_mut()
+            .expect("no segment to record to")
+            .push(point);
+        self.last = Some(point);
+        true
+    }
+}
+
+impl Default for TrailRecorder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buffer = [0; N];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn write_point(writer: &mut impl Write, point: [f32; 3]) -> io::Result<()> {
+    for value in point {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trail() -> Trail {
+        Trail {
+            map_id: 1206,
+            segments: vec![
+                vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]],
+                Vec::new(),
+                vec![[-7.5, 8.25, 9.0]],
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut data = Vec::new();
+        trail().write_to(&mut data).unwrap();
+        assert_eq!(data.len(), 8 + 4 * 12);
+        assert_eq!(data[..4], VERSION.to_le_bytes());
+        assert_eq!(data[4..8], 1206u32.to_le_bytes());
+
+        let read = Trail::read_from(data.as_slice()).unwrap();
+        assert_eq!(read.map_id, 1206);
+        assert_eq!(
+            read.segments,
+            [
+                vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]],
+                vec![[-7.5, 8.25, 9.0]]
+            ]
+        );
+    }
+
+    #[test]
+    fn separators() {
+        let mut data = Vec::new();
+        data.extend(VERSION.to_le_bytes());
+        data.extend(15u32.to_le_bytes());
+        for point in [[0.0; 3], [1.0; 3], [0.0; 3], [0.0; 3], [2.0; 3], [0.0; 3]] {
+            write_point(&mut data, point).unwrap();
+        }
+        let trail = Trail::read_from(data.as_slice()).unwrap();
+        assert_eq!(trail.segments, [vec![[1.0; 3]], vec![[2.0; 3]]]);
+    }
+
+    #[test]
+    fn invalid() {
+        let mut data = Vec::new();
+        trail().write_to(&mut data).unwrap();
+
+        assert!(matches!(
+            Trail::read_from(&data[..data.len() - 1]),
+            Err(TrailError::InvalidLength(47))
+        ));
+        assert!(matches!(
+            Trail::read_from(&data[..6]),
+            Err(TrailError::IoError(_))
+        ));
+        assert!(matches!(
+            Trail::read_from(&[][..]),
+            Err(TrailError::IoError(_))
+        ));
+
+        data[0] = 1;
+        assert!(matches!(
+            Trail::read_from(data.as_slice()),
+            Err(TrailError::UnsupportedVersion(1))
+        ));
+    }
+
+    #[test]
+    fn min_distance() {
+        let mut recorder = TrailRecorder::with_distances(1.0, 50.0);
+        assert!(recorder.push(15, [10.0, 0.0, 0.0]));
+        assert!(!recorder.push(15, [10.5, 0.0, 0.0]));
+        assert!(recorder.push(15, [11.0, 0.0, 0.0]));
+        assert!(!recorder.push(15, [11.0, 0.0, 0.9]));
+        assert!(!recorder.push(0, [20.0, 0.0, 0.0]));
+        assert!(!recorder.push(15, [0.0; 3]));
+        assert!(!recorder.push(15, [f32::NAN, 0.0, 0.0]));
+        assert_eq!(
+            recorder.trails(),
+            [Trail {
+                map_id: 15,
+                segments: vec![vec![[10.0, 0.0, 0.0], [11.0, 0.0, 0.0]]],
+            }]
+        );
+    }
+
+    #[test]
+    fn splits() {
+        let mut recorder = TrailRecorder::with_distances(1.0, 50.0);
+        recorder.push(15, [10.0, 0.0, 0.0]);
+        recorder.push(15, [40.0, 0.0, 0.0]);
+        recorder.push(15, [100.0, 0.0, 0.0]);
+        recorder.push(50, [105.0, 0.0, 0.0]);
+        recorder.push(50, [110.0, 0.0, 0.0]);
+        recorder.push(15, [120.0, 0.0, 0.0]);
+        recorder.split();
+        recorder.push(15, [125.0, 0.0, 0.0]);
+
+        assert_eq!(recorder.current().map(|trail| trail.map_id), Some(15));
+        assert_eq!(
+            recorder.into_trails(),
+            [
+                Trail {
+                    map_id: 15,
+                    segments: vec![
+                        vec![[10.0, 0.0, 0.0], [40.0, 0.0, 0.0]],
+                        vec![[100.0, 0.0, 0.0]],
+                        vec![[120.0, 0.0, 0.0]],
+                        vec![[125.0, 0.0, 0.0]],
+                    ],
+                },
+                Trail {
+                    map_id: 50,
+                    segments: vec![vec![[105.0, 0.0, 0.0], [110.0, 0.0, 0.0]]],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sample() {
+        let mut mem = crate::LinkedMem::default();
+        mem.context.map_id = 15;
+        mem.avatar.position = [1.0, 2.0, 3.0];
+        let mut recorder = TrailRecorder::new();
+        assert!(recorder.sample(&mem));
+        assert!(!recorder.sample(&mem));
+        assert_eq!(recorder.trails()[0].segments, [vec![[1.0, 2.0, 3.0]]]);
+    }
+}
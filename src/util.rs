@@ -13,8 +13,70 @@ pub fn read_until_nul<const N: usize>(ptr: *const [u16; N]) -> Vec<u16> {
     vec
 }
 
-/// Returns the subslice until the first `0`.
-pub fn until_nul(slice: &[u16]) -> &[u16] {
-    let end = slice.iter().position(|el| *el == 0).unwrap_or(slice.len());
-    &slice[..end]
+/// Encodes a string as nul-terminated wide chars.
+///
+/// Truncates the string to leave space for the terminating `0`.
+pub fn encode_wide<const N: usize>(string: &str) -> [u16; N] {
+    let mut buffer = [0; N];
+    let len = N.saturating_sub(1);
+    for (dest, src) in buffer[..len].iter_mut().zip(string.encode_utf16()) {
+        *dest = src;
+    }
+
+    // avoid leaving half of a truncated surrogate pair
+    if let Some(last) = buffer[..len].last_mut() {
+        if (0xd800..0xdc00).contains(last) {
+            *last = 0;
+        }
+    }
+    buffer
+}
+
+/// Implements serde traits via the primitive representation.
+///
+/// Used for enums with catch-all variants, which can not derive the traits.
+macro_rules! serde_primitive {
+    ( $ty:ty => $repr:ty ) => {
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $ty {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                <$repr as ::serde::Serialize>::serialize(&(*self).into(), serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $ty {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                <$repr as ::serde::Deserialize>::deserialize(deserializer).map(Self::from)
+            }
+        }
+    };
 }
+
+pub(crate) use serde_primitive;
+
+/// Implements [`Display`](std::fmt::Display) via the [`AsRef<str>`] name.
+///
+/// Used for enums with catch-all variants, which strum displays without the unknown value.
+macro_rules! display_unknown {
+    ( $ty:ty ) => {
+        impl ::std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    Self::Unknown(value) => f.pad(&format!("Unknown({value})")),
+                    _ => f.pad(self.as_ref()),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use display_unknown;
@@ -0,0 +1,219 @@
+use crate::{
+    decode_wide_lossy, until_nul, LinkSource, LinkedMem, Liveness, LivenessState, Mount, UiState,
+};
+use std::time::{Duration, Instant};
+
+/// Change detected between successive [`LinkedMem`] reads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Map id changed.
+    MapChanged { from: u32, to: u32 },
+
+    /// Currently used mount changed.
+    MountChanged { from: Mount, to: Mount },
+
+    /// UI state bitflags changed.
+    UiStateChanged { added: UiState, removed: UiState },
+
+    /// Identity JSON changed, for example due to a character swap or specialization change.
+    IdentityChanged { from: String, to: String },
+
+    /// Build id changed.
+    BuildChanged { from: u32, to: u32 },
+
+    /// Instance id changed.
+    InstanceChanged { from: u32, to: u32 },
+
+    /// Server address changed.
+    ServerChanged { from: [u8; 28], to: [u8; 28] },
+
+    /// The game exited.
+    ///
+    /// Detected by the [`Liveness`] tracker after the game was seen running.
+    /// Loading screens and character select do not trigger this as long as the game process is alive.
+    GameClosed,
+}
+
+/// Watches successive [`LinkedMem`] reads for changes.
+///
+/// The first read only establishes the baseline and does not produce any events.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    previous: Option<LinkedMem>,
+    liveness: Liveness,
+    closed: bool,
+}
+
+impl Watcher {
+    /// Default time without `ui_tick` advancing before [`Event::GameClosed`] is emitted.
+    ///
+    /// Only applies if the game process can not be checked, see [`Liveness`].
+    /// Raised from 10 to 60 seconds to not mistake long loading screens for a closed game.
+    pub const DEFAULT_TIMEOUT: Duration = Liveness::DEFAULT_EXIT_TIMEOUT;
+
+    /// Creates a new watcher with the default timeout.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_timeout(Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Creates a new watcher with the given timeout for [`Event::GameClosed`].
+    ///
+    /// The timeout is the exit timeout of the underlying [`Liveness`] tracker.
+    /// It only applies if the game process can not be checked, which is the default on platforms other than Windows.
+    /// Otherwise [`Event::GameClosed`] is emitted once the `ui_tick` stalls and the process exited.
+    /// The default changed from 10 to 60 seconds, see [`Watcher::DEFAULT_TIMEOUT`].
+    #[inline]
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_liveness(Liveness::with_thresholds(
+            Liveness::DEFAULT_PAUSE_THRESHOLD,
+            timeout,
+        ))
+    }
+
+    /// Creates a new watcher using the given [`Liveness`] tracker for [`Event::GameClosed`].
+    #[inline]
+    pub fn with_liveness(liveness: Liveness) -> Self {
+        Self {
+            previous: None,
+            liveness,
+            closed: false,
+        }
+    }
+
+    /// Returns the previously read [`LinkedMem`].
+    #[inline]
+    pub fn previous(&self) -> Option<&LinkedMem> {
+        self.previous.as_ref()
+    }
+
+    /// Reads from the source and returns the changes since the previous read.
+    #[inline]
+    pub fn poll(&mut self, source: &impl LinkSource) -> Vec<Event> {
+        self.update(source.read())
+    }
+
+    /// Returns the changes between the previous and the given [`LinkedMem`].
+    #[inline]
+    pub fn update(&mut self, mem: LinkedMem) -> Vec<Event> {
+        self.update_at(mem, Instant::now())
+    }
+
+    /// Returns the changes between the previous and the given [`LinkedMem`] read at the given time.
+    pub fn update_at(&mut self, mem: LinkedMem, now: Instant) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        if let Some(prev) = &self.previous {
+            let (old, new) = (&prev.context, &mem.context);
+
+            if old.map_id != new.map_id {
+                events.push(Event::MapChanged {
+                    from: old.map_id,
+                    to: new.map_id,
+                });
+            }
+            if old.mount_index != new.mount_index {
+                events.push(Event::MountChanged {
+                    from: old.mount_index.into(),
+                    to: new.mount_index.into(),
+                });
+            }
+            if old.ui_state != new.ui_state {
+                let old = UiState::from_bits_retain(old.ui_state);
+                let new = UiState::from_bits_retain(new.ui_state);
+                events.push(Event::UiStateChanged {
+                    added: new.difference(old),
+                    removed: old.difference(new),
+                });
+            }
+            if until_nul(&prev.identity) != until_nul(&mem.identity) {
+                events.push(Event::IdentityChanged {
+                    from: decode_wide_lossy(&prev.identity),
+                    to: decode_wide_lossy(&mem.identity),
+                });
+            }
+            if old.build_id != new.build_id {
+                events.push(Event::BuildChanged {
+                    from: old.build_id,
+                    to: new.build_id,
+                });
+            }
+            if old.instance != new.instance {
+                events.push(Event::InstanceChanged {
+                    from: old.instance,
+                    to: new.instance,
+                });
+            }
+            if old.server_address != new.server_address {
+                events.push(Event::ServerChanged {
+                    from: old.server_address,
+                    to: new.server_address,
+                });
+            }
+        }
+
+        let exited = self.liveness.update_at(&mem, now) == LivenessState::Exited;
+        if exited && !self.closed && self.liveness.has_advanced() {
+            events.push(Event::GameClosed);
+        }
+        self.closed = exited;
+
+        self.previous = Some(mem);
+        events
+    }
+}
+
+impl Default for Watcher {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem(tick: u32) -> LinkedMem {
+        crate::test_util::mem(tick, 42)
+    }
+
+    fn watcher() -> Watcher {
+        Watcher::with_liveness(Liveness::new().with_process_check(false))
+    }
+
+    #[test]
+    fn loading_screen() {
+        let mut watcher = watcher();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(watcher.update_at(mem(1), at(0)).is_empty());
+        assert!(watcher.update_at(mem(2), at(1)).is_empty());
+        assert!(watcher.update_at(mem(2), at(30)).is_empty());
+        assert!(watcher.update_at(mem(3), at(31)).is_empty());
+    }
+
+    #[test]
+    fn game_closed() {
+        let mut watcher = watcher();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        watcher.update_at(mem(1), at(0));
+        watcher.update_at(mem(2), at(1));
+        assert!(watcher.update_at(mem(2), at(50)).is_empty());
+        assert_eq!(watcher.update_at(mem(2), at(70)), [Event::GameClosed]);
+        assert!(watcher.update_at(mem(2), at(80)).is_empty());
+    }
+
+    #[test]
+    fn stale() {
+        let mut watcher = watcher();
+        let start = Instant::now();
+        for secs in [0, 10, 70, 100] {
+            let now = start + Duration::from_secs(secs);
+            assert!(watcher.update_at(mem(1), now).is_empty());
+        }
+    }
+}
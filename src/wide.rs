@@ -0,0 +1,154 @@
+use crate::DecodeError;
+use std::{
+    char::{DecodeUtf16, REPLACEMENT_CHARACTER},
+    iter::{Copied, FusedIterator},
+    slice,
+};
+
+/// Returns the subslice until the first `0`.
+#[inline]
+pub(crate) fn until_nul(slice: &[u16]) -> &[u16] {
+    let end = slice.iter().position(|el| *el == 0).unwrap_or(slice.len());
+    &slice[..end]
+}
+
+/// Decodes wide chars until the first `0` as UTF-16.
+///
+/// Fails on the first unpaired surrogate.
+pub fn decode_wide(slice: &[u16]) -> Result<String, DecodeError> {
+    decode_chars(slice).collect()
+}
+
+/// Decodes wide chars until the first `0` as UTF-16.
+///
+/// Replaces unpaired surrogates with [`REPLACEMENT_CHARACTER`].
+pub fn decode_wide_lossy(slice: &[u16]) -> String {
+    decode_chars(slice)
+        .map(|result| result.unwrap_or(REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Returns an iterator decoding the wide chars until the first `0` as UTF-16.
+///
+/// Does not copy the underlying wide chars.
+#[inline]
+pub fn decode_chars(slice: &[u16]) -> WideChars<'_> {
+    WideChars {
+        chars: char::decode_utf16(until_nul(slice).iter().copied()),
+        offset: 0,
+    }
+}
+
+/// Iterator over [`char`]s decoded from UTF-16 wide chars.
+///
+/// See [`decode_chars`].
+#[derive(Debug, Clone)]
+pub struct WideChars<'a> {
+    chars: DecodeUtf16<Copied<slice::Iter<'a, u16>>>,
+    offset: usize,
+}
+
+impl WideChars<'_> {
+    /// Returns the offset of the next code unit.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Iterator for WideChars<'_> {
+    type Item = Result<char, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.offset;
+        let result = match self.chars.next()? {
+            Ok(char) => {
+                self.offset += char.len_utf16();
+                Ok(char)
+            }
+            Err(err) => {
+                self.offset += 1;
+                Err(DecodeError {
+                    offset,
+                    unit: err.unpaired_surrogate(),
+                })
+            }
+        };
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+impl FusedIterator for WideChars<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide(string: &str) -> Vec<u16> {
+        string.encode_utf16().collect()
+    }
+
+    #[test]
+    fn decode() {
+        let mut units = wide("a😀ä");
+        units.extend([0, 0x62]);
+        assert_eq!(decode_wide(&units).unwrap(), "a😀ä");
+        assert_eq!(decode_wide(&[0; 4]).unwrap(), "");
+        assert_eq!(decode_wide(&[]).unwrap(), "");
+
+        let mut chars = decode_chars(&units);
+        assert_eq!(chars.next(), Some(Ok('a')));
+        assert_eq!(chars.offset(), 1);
+        assert_eq!(chars.next(), Some(Ok('😀')));
+        assert_eq!(chars.offset(), 3);
+        assert_eq!(chars.next(), Some(Ok('ä')));
+        assert_eq!(chars.offset(), 4);
+        assert_eq!(chars.next(), None);
+        assert_eq!(chars.next(), None);
+    }
+
+    #[test]
+    fn missing_nul() {
+        let units = wide("Guild Wars 2");
+        assert_eq!(until_nul(&units), units);
+        assert_eq!(decode_wide(&units).unwrap(), "Guild Wars 2");
+        assert_eq!(decode_chars(&units).count(), 12);
+    }
+
+    #[test]
+    fn lone_surrogates() {
+        let units = [0x61, 0xd83d, 0x62, 0xde00, 0xd83d];
+        assert_eq!(
+            decode_wide(&units),
+            Err(DecodeError {
+                offset: 1,
+                unit: 0xd83d
+            })
+        );
+        assert_eq!(decode_wide_lossy(&units), "a\u{fffd}b\u{fffd}\u{fffd}");
+        assert_eq!(
+            decode_chars(&units).collect::<Vec<_>>(),
+            [
+                Ok('a'),
+                Err(DecodeError {
+                    offset: 1,
+                    unit: 0xd83d
+                }),
+                Ok('b'),
+                Err(DecodeError {
+                    offset: 3,
+                    unit: 0xde00
+                }),
+                Err(DecodeError {
+                    offset: 4,
+                    unit: 0xd83d
+                }),
+            ]
+        );
+    }
+}
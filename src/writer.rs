@@ -0,0 +1,164 @@
+use crate::{
+    mapping::Mapping, util::encode_wide, Context, Error, LinkedMem, MumbleLink, MumblePtr,
+    Position, RawContext,
+};
+#[cfg(unix)]
+use std::path::Path;
+use std::ptr::NonNull;
+
+/// Writable access point to a MumbleLink memory shared file.
+///
+/// Allows publishing [`LinkedMem`] data the same way Guild Wars 2 does, for example for tests or simulators.
+#[derive(Debug)]
+pub struct MumbleWriter {
+    mapping: Mapping,
+}
+
+impl MumbleWriter {
+    /// UI version written by Guild Wars 2.
+    pub const UI_VERSION: u32 = 2;
+
+    /// Game name written by Guild Wars 2.
+    pub const NAME: &'static str = "Guild Wars 2";
+
+    /// Context length written by Guild Wars 2.
+    pub const CONTEXT_LEN: u32 = 48;
+
+    /// Creates a new writable MumbleLink.
+    ///
+    /// The name is resolved via [`MumbleLink::link_name`].
+    #[inline]
+    pub fn new() -> Result<Self, Error> {
+        Self::with_name(&MumbleLink::link_name())
+    }
+
+    /// Creates a new writable MumbleLink with the given name.
+    ///
+    /// See [`MumbleLink::with_name`] for details.
+    #[inline]
+    pub fn with_name(name: &str) -> Result<Self, Error> {
+        Mapping::open(name, true).map(|mapping| Self { mapping })
+    }
+
+    /// Creates a new writable MumbleLink backed by the file at the given path.
+    ///
+    /// The file is created if necessary.
+    #[inline]
+    #[cfg(unix)]
+    pub fn create_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Mapping::open_path(path, true).map(|mapping| Self { mapping })
+    }
+
+    /// Returns a [`MumblePtr`] for reading the written data.
+    #[inline]
+    pub fn as_mumble_ptr(&self) -> MumblePtr {
+        unsafe { MumblePtr::new(self.as_ptr()) }.expect("mapping is null")
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut LinkedMem {
+        NonNull::as_ptr(self.mapping.as_non_null())
+    }
+
+    /// Writes the header fields the same way Guild Wars 2 does.
+    ///
+    /// This sets `ui_version`, `name` and `context_len`.
+    pub fn init(&mut self) {
+        self.set_ui_version(Self::UI_VERSION);
+        self.set_name(Self::NAME);
+        write_member!(self.context_len = Self::CONTEXT_LEN);
+    }
+
+    /// Writes the entire [`LinkedMem`] contents.
+    #[inline]
+    pub fn write(&mut self, mem: &LinkedMem) {
+        unsafe { self.as_ptr().write_volatile(mem.clone()) }
+    }
+
+    /// Sets the `ui_version`.
+    #[inline]
+    pub fn set_ui_version(&mut self, version: u32) {
+        write_member!(self.ui_version = version)
+    }
+
+    /// Sets the `ui_tick`.
+    #[inline]
+    pub fn set_ui_tick(&mut self, tick: u32) {
+        write_member!(self.ui_tick = tick)
+    }
+
+    /// Advances the `ui_tick` by one like Guild Wars 2 does each frame.
+    ///
+    /// Returns the new tick.
+    #[inline]
+    pub fn tick(&mut self) -> u32 {
+        let tick = self.as_mumble_ptr().read_ui_tick().wrapping_add(1);
+        self.set_ui_tick(tick);
+        tick
+    }
+
+    /// Sets the player avatar [`Position`].
+    #[inline]
+    pub fn set_avatar(&mut self, avatar: &Position) {
+        write_member!(self.avatar = avatar.clone())
+    }
+
+    /// Sets the game name.
+    ///
+    /// The name is truncated to fit into the buffer.
+    #[inline]
+    pub fn set_name(&mut self, name: &str) {
+        write_member!(self.name = encode_wide(name))
+    }
+
+    /// Sets the camera [`Position`].
+    #[inline]
+    pub fn set_camera(&mut self, camera: &Position) {
+        write_member!(self.camera = camera.clone())
+    }
+
+    /// Sets the raw player identity JSON.
+    ///
+    /// The JSON is truncated to fit into the buffer.
+    #[inline]
+    pub fn set_identity_json(&mut self, json: &str) {
+        write_member!(self.identity = encode_wide(json))
+    }
+
+    /// Sets the player [`Identity`](crate::Identity) encoded as JSON.
+    #[cfg(feature = "json")]
+    pub fn set_identity(&mut self, identity: &crate::Identity) -> serde_json::Result<()> {
+        let json = serde_json::to_string(identity)?;
+        self.set_identity_json(&json);
+        Ok(())
+    }
+
+    /// Sets the [`Context`].
+    #[inline]
+    pub fn set_context(&mut self, context: &Context) {
+        self.set_raw_context(&context.into())
+    }
+
+    /// Sets the [`RawContext`].
+    #[inline]
+    pub fn set_raw_context(&mut self, context: &RawContext) {
+        write_member!(self.context = context.clone())
+    }
+
+    /// Sets the game description.
+    ///
+    /// The description is truncated to fit into the buffer.
+    #[inline]
+    pub fn set_description(&mut self, description: &str) {
+        write_member!(self.description = encode_wide(description))
+    }
+}
+
+macro_rules! write_member {
+    ( $self:ident $( .$member:ident )+ = $value:expr ) => {{
+        let value = $value;
+        unsafe { ::std::ptr::addr_of_mut!( (*$self.as_ptr()) $( .$member )+ ).write_volatile(value) }
+    }};
+}
+
+use write_member;